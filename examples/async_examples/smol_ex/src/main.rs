@@ -0,0 +1,43 @@
+use fltk::{app, button::Button, frame::Frame, group::Flex, prelude::*, window::Window};
+use fltk_evented::AsyncListener;
+
+fn main() {
+    let a = app::App::default().with_scheme(app::Scheme::Gtk);
+    app::set_font_size(20);
+
+    let mut wind = Window::default()
+        .with_size(160, 200)
+        .with_label("Counter");
+    let flex = Flex::default()
+        .with_size(120, 160)
+        .center_of_parent()
+        .column();
+    let but_inc: AsyncListener<_> = Button::default().with_label("+").into();
+    let mut frame = Frame::default().with_label("0");
+    let but_dec: AsyncListener<_> = Button::default().with_label("-").into();
+    flex.end();
+    wind.end();
+    wind.show();
+
+    smol::spawn(async move {
+        let mut count = 0;
+        loop {
+            count += smol::future::or(
+                async {
+                    but_inc.wait_triggered().await;
+                    1
+                },
+                async {
+                    but_dec.wait_triggered().await;
+                    -1
+                },
+            )
+            .await;
+            frame.set_label(&count.to_string());
+            app::awake();
+        }
+    })
+    .detach();
+
+    a.run().unwrap();
+}
@@ -18,20 +18,20 @@ fn main() {
     wind.end();
     wind.show();
 
-    but.on_hover(|b| {
+    but.on_hover(|b, _| {
         b.set_color(Color::Cyan.lighter().lighter());
     });
 
-    but.on_leave(|b| {
+    but.on_leave(|b, _| {
         b.set_color(Color::Cyan);
     });
 
-    but.on_click(|b| {
+    but.on_click(|b, _| {
         println!("Clicked");
         b.set_label_color(Color::White);
     });
 
-    but.on_release(move |b| {
+    but.on_release(move |b, _| {
         wind.set_label("Button Released!");
         b.set_label_color(Color::Black);
     });
@@ -1,72 +1,453 @@
 use crate::base::BaseListener;
+#[cfg(feature = "futures")]
+use crate::broadcast::Broadcast;
+use crate::chain::Previous;
+use crate::handlers::{dispatch, on_events, Handler, HandlerId, Handlers, Phase, Propagation};
+use crate::info::{EventInfo, TriggerInfo};
+use crate::queue::{EventQueue, Overflow, TriggerLog};
+use crate::runtime::{default_spawner, Overlap, Runner, Spawner};
 use fltk::{
-    app,
     enums::Event,
     prelude::{WidgetBase, WidgetExt},
 };
-use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
-use std::sync::Arc;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::{Arc, Mutex, TryLockError};
+use std::task::{Context, Poll, Waker};
 
-#[cfg(feature = "tokio")]
-use tokio::spawn;
+/// Tasks waiting on a listener, woken from the UI thread
+#[derive(Debug, Clone, Default)]
+struct Wakers(Arc<Mutex<Vec<Waker>>>);
 
-#[cfg(feature = "async-std")]
-use async_std::task::spawn;
+impl Wakers {
+    fn register(&self, waker: &Waker) {
+        let mut wakers = self.0.lock().unwrap();
+        if !wakers.iter().any(|w| w.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+    }
+
+    fn wake_all(&self) {
+        let wakers = std::mem::take(&mut *self.0.lock().unwrap());
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+type HandlerFn<T> = Box<dyn FnMut(&mut T, &EventInfo) -> Propagation + Send>;
+type SharedHandler<T> = Arc<Mutex<HandlerFn<T>>>;
+
+impl<T> Handler<T> for SharedHandler<T> {
+    fn call(&self, w: &mut T, info: &EventInfo) -> Option<Propagation> {
+        let mut cb = match self.try_lock() {
+            Ok(cb) => cb,
+            // a handler that panicked once, caught by fltk-rs, keeps running on later events
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+            Err(TryLockError::WouldBlock) => return None,
+        };
+        Some(cb(w, info))
+    }
+}
+
+pub struct Trig<T> {
+    triggers: Arc<Mutex<TriggerLog>>,
+    trigger_wakers: Wakers,
+    queue: Arc<Mutex<EventQueue>>,
+    event_wakers: Wakers,
+    #[cfg(feature = "futures")]
+    trigger_subs: Broadcast<()>,
+    #[cfg(feature = "futures")]
+    event_subs: Broadcast<EventInfo>,
+    handlers: Arc<Mutex<Handlers<SharedHandler<T>>>>,
+    spawner: Arc<Mutex<Option<Arc<dyn Spawner>>>>,
+}
+
+impl<T> Clone for Trig<T> {
+    fn clone(&self) -> Self {
+        Self {
+            triggers: self.triggers.clone(),
+            trigger_wakers: self.trigger_wakers.clone(),
+            queue: self.queue.clone(),
+            event_wakers: self.event_wakers.clone(),
+            #[cfg(feature = "futures")]
+            trigger_subs: self.trigger_subs.clone(),
+            #[cfg(feature = "futures")]
+            event_subs: self.event_subs.clone(),
+            handlers: self.handlers.clone(),
+            spawner: self.spawner.clone(),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for Trig<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Trig")
+            .field("triggers", &self.triggers)
+            .field("queue", &self.queue)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Trig<T> {
+    /// Record a trigger and wake the tasks waiting for one
+    fn trigger(&self, info: TriggerInfo) {
+        self.triggers.lock().unwrap().push(info);
+        #[cfg(feature = "futures")]
+        self.trigger_subs.send(());
+        self.trigger_wakers.wake_all();
+    }
+
+    /// Queue an event and wake the tasks waiting for one
+    fn record(&self, info: EventInfo) {
+        #[cfg(feature = "futures")]
+        if self.event_subs.has_subscribers() {
+            self.event_subs.send(info.clone());
+        }
+        self.queue.lock().unwrap().push(info);
+        self.event_wakers.wake_all();
+    }
+
+    /// Consume the pending triggers, returns whether there were any
+    fn take_triggered(&self) -> bool {
+        self.triggers.lock().unwrap().take_count() > 0
+    }
+
+    /// Consume the pending triggers, or register the task to be woken by the next one
+    fn poll_triggered(&self, cx: &mut Context<'_>) -> Poll<()> {
+        if self.take_triggered() {
+            return Poll::Ready(());
+        }
+        self.trigger_wakers.register(cx.waker());
+        // a trigger might have landed before the waker was registered
+        if self.take_triggered() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
 
-#[derive(Debug, Clone)]
-pub struct Trig {
-    triggered: Arc<AtomicBool>,
-    event: Arc<AtomicI32>,
+    /// Pop the next queued event, or register the task to be woken by the next one
+    fn poll_event(&self, cx: &mut Context<'_>) -> Poll<EventInfo> {
+        if let Some(info) = self.queue.lock().unwrap().pop() {
+            return Poll::Ready(info);
+        }
+        self.event_wakers.register(cx.waker());
+        match self.queue.lock().unwrap().pop() {
+            Some(info) => Poll::Ready(info),
+            None => Poll::Pending,
+        }
+    }
 }
 
+impl<T> crate::value::sealed::ValueSet<T> for Trig<T> {}
+
 /// The async widget listener recieves both `triggered: bool` from [`AsyncListener<T>::triggered()`],
-/// and [`Event`] from [`AsyncListener<T>::event()`].
-pub type AsyncListener<T> = BaseListener<T, Trig>;
+/// and the queued [`Event`]s from [`AsyncListener<T>::event()`] or [`AsyncListener<T>::wait_event()`].
+/// Triggers and events are recorded on the UI thread as they happen, only waiting tasks are woken.
+pub type AsyncListener<T> = BaseListener<T, Trig<T>>;
 
 /// core constructor
-impl<T: WidgetBase + WidgetExt> From<T> for AsyncListener<T> {
-    fn from(mut wid: T) -> Self {
-        let triggered = Arc::new(AtomicBool::new(false));
+impl<T: WidgetBase + WidgetExt + 'static> From<T> for AsyncListener<T> {
+    fn from(wid: T) -> Self {
+        Self::listen(wid, Previous::default())
+    }
+}
+
+impl<T: WidgetBase + WidgetExt + 'static> AsyncListener<T> {
+    /// Wrap a widget while keeping the callback and handler it already had,
+    /// like ones set by a custom widget or fl2rust-generated code.
+    /// The previous callback runs before the listener records the trigger,
+    /// the previous handler runs after the widget's native handling as it did before,
+    /// ahead of the listener's `after_native` handlers.
+    /// While the listener has `before_native` handlers, events without `after_native` handlers
+    /// reach the previous handler ahead of native handling.
+    /// # Safety
+    /// The widget's callback and handler, if any, must have been set through `T::set_callback` and `T::handle`,
+    /// and not through another widget type wrapping the same FLTK widget
+    pub unsafe fn chained(wid: T) -> Self {
+        let previous = Previous::take(&wid);
+        Self::listen(wid, previous)
+    }
+
+    fn listen(mut wid: T, previous: Previous<T>) -> Self {
+        let trig = Trig {
+            triggers: Arc::default(),
+            trigger_wakers: Wakers::default(),
+            queue: Arc::new(Mutex::new(EventQueue::default())),
+            event_wakers: Wakers::default(),
+            #[cfg(feature = "futures")]
+            trigger_subs: Broadcast::default(),
+            #[cfg(feature = "futures")]
+            event_subs: Broadcast::default(),
+            handlers: Arc::default(),
+            spawner: Arc::new(Mutex::new(default_spawner())),
+        };
         wid.set_callback({
-            let triggered = triggered.clone();
-            move |_| {
-                let triggered = triggered.clone();
-                spawn(async move {
-                    triggered.store(true, Ordering::Relaxed);
-                    app::awake();
-                });
+            let trig = trig.clone();
+            let previous = previous.clone();
+            move |w| {
+                previous.callback(w);
+                trig.trigger(TriggerInfo::capture());
             }
         });
-        let event = Arc::new(AtomicI32::new(Event::NoEvent.bits()));
+        let is_window = wid.as_window().is_some();
+        // FLTK runs native handling ahead of the listener until `before_native` handlers are registered
+        wid.super_handle_first(true);
+        let native_pending = Cell::new(None);
         wid.handle({
-            let event = event.clone();
-            move |_, evt| {
-                let event = event.clone();
-                spawn(async move {
-                    event.store(evt.bits(), Ordering::Relaxed);
-                    app::awake();
-                });
-                false
+            let trig = trig.clone();
+            move |w, evt| {
+                // re-entered by `dispatch` to run native handling, see there
+                if native_pending.get() == Some(evt) {
+                    native_pending.set(None);
+                    return false;
+                }
+                let info = EventInfo::capture(evt, (!is_window).then(|| (w.x(), w.y())));
+                let (native_ran, before, after) = {
+                    let handlers = trig.handlers.lock().unwrap();
+                    let (before, after) = handlers.get_phases(evt);
+                    (!handlers.has_phase(Phase::BeforeNative), before, after)
+                };
+                trig.record(info.clone());
+                dispatch(
+                    w,
+                    &info,
+                    native_ran,
+                    before,
+                    &previous,
+                    after,
+                    &native_pending,
+                )
+                .handled
             }
         });
-        let trig = Trig { triggered, event };
         Self { wid, trig }
     }
 }
 
 /// core implementation
-impl<T: WidgetBase + WidgetExt> AsyncListener<T> {
-    /// Check whether a widget was triggered
+impl<T: WidgetBase + WidgetExt + 'static> AsyncListener<T> {
+    /// Check whether a widget was triggered since the last poll, and reset it.
+    /// The trigger records are kept, poll them with `while let Some(info) = w.trigger_info()`
     pub async fn triggered(&self) -> bool {
-        self.trig.triggered.swap(false, Ordering::Relaxed)
+        self.trig.take_triggered()
     }
 
-    /// Get an event the widget received,
-    /// returns [`Event::NoEvent`] if no events received
+    /// Check whether a widget was triggered, without resetting it
+    pub fn peek_triggered(&self) -> bool {
+        self.trig.triggers.lock().unwrap().count() > 0
+    }
+
+    /// Get the number of times the widget was triggered since the last poll, and reset it.
+    /// The trigger records are kept for [`trigger_info`](Self::trigger_info)
+    pub fn trigger_count(&self) -> usize {
+        self.trig.triggers.lock().unwrap().take_count()
+    }
+
+    /// Take the oldest trigger record along with why it happened, whether or not `triggered` was polled,
+    /// returns `None` once all the records were taken
+    pub fn trigger_info(&self) -> Option<TriggerInfo> {
+        self.trig.triggers.lock().unwrap().pop()
+    }
+
+    /// Drain the trigger records since the last poll, oldest first
+    pub(crate) fn take_triggers(&self) -> VecDeque<TriggerInfo> {
+        self.trig.triggers.lock().unwrap().take()
+    }
+
+    /// Wake the task on the next trigger
+    pub(crate) fn register_trigger_waker(&self, waker: &Waker) {
+        self.trig.trigger_wakers.register(waker);
+    }
+
+    /// Wait until the widget is triggered, resolves at once if a trigger is pending.
+    /// Lets a task `loop { btn.wait_triggered().await; ... }` without polling
+    pub fn wait_triggered(&self) -> impl Future<Output = ()> + Send + 'static {
+        let trig = self.trig.clone();
+        std::future::poll_fn(move |cx| trig.poll_triggered(cx))
+    }
+
+    /// Get the next event the widget received,
+    /// returns [`Event::NoEvent`] if no events are queued
     pub fn event(&self) -> Event {
+        self.event_info().map_or(Event::NoEvent, |info| info.event)
+    }
+
+    /// Get the next event the widget received along with the event state at the time,
+    /// returns `None` if no events are queued
+    pub fn event_info(&self) -> Option<EventInfo> {
+        self.trig.queue.lock().unwrap().pop()
+    }
+
+    /// Get the next event the widget received without removing it from the queue,
+    /// returns [`Event::NoEvent`] if no events are queued
+    pub fn peek_event(&self) -> Event {
         self.trig
-            .event
-            .swap(Event::NoEvent.bits(), Ordering::Relaxed)
-            .into()
+            .queue
+            .lock()
+            .unwrap()
+            .peek()
+            .map_or(Event::NoEvent, |info| info.event)
+    }
+
+    /// Wait for the next event the widget receives, resolves at once if events are queued
+    pub fn wait_event(&self) -> impl Future<Output = EventInfo> + Send + 'static {
+        let trig = self.trig.clone();
+        std::future::poll_fn(move |cx| trig.poll_event(cx))
+    }
+
+    /// Set how many events are kept between polls, see [`DEFAULT_QUEUE_CAPACITY`](crate::DEFAULT_QUEUE_CAPACITY).
+    /// Shrinking the queue discards the oldest events
+    pub fn set_queue_capacity(&mut self, capacity: usize) {
+        self.trig.queue.lock().unwrap().set_capacity(capacity);
+    }
+
+    /// Set what happens when an event arrives and the queue is full
+    pub fn set_overflow(&mut self, overflow: Overflow) {
+        self.trig.queue.lock().unwrap().set_overflow(overflow);
+    }
+
+    /// Stream the events the widget receives from now on, along with their event state.
+    /// Each stream buffers events independently of the others
+    #[cfg(feature = "futures")]
+    pub fn events(&self) -> impl futures_core::Stream<Item = EventInfo> + Unpin + Send + 'static {
+        self.trig.event_subs.subscribe()
+    }
+
+    /// Stream the widget's triggers from now on, one item per callback invocation
+    #[cfg(feature = "futures")]
+    pub fn triggers(&self) -> impl futures_core::Stream<Item = ()> + Unpin + Send + 'static {
+        self.trig.trigger_subs.subscribe()
+    }
+
+    /// Set the spawner that runs the futures started by [`AsyncListener::on_async`].
+    /// Defaults to the runtime of the enabled tokio, async-std or smol feature, in that order,
+    /// tokio only when spawning from within a tokio runtime
+    pub fn set_spawner(&mut self, spawner: impl Spawner) {
+        *self.trig.spawner.lock().unwrap() = Some(Arc::new(spawner));
+    }
+
+    /// Initialize with a spawner, see [`AsyncListener::set_spawner`]
+    pub fn with_spawner(mut self, spawner: impl Spawner) -> Self {
+        self.set_spawner(spawner);
+        self
+    }
+
+    /// What the widget should do on a custom event, after the widget's native handling.
+    /// Handlers stack, they run in registration order on the UI thread
+    pub fn on<P: Into<Propagation>>(
+        &mut self,
+        ev: Event,
+        cb: impl FnMut(&mut T, &EventInfo) -> P + Send + 'static,
+    ) -> HandlerId {
+        self.after_native(ev, cb)
     }
+
+    /// What the widget should do on a custom event, before the widget's native handling.
+    /// Returning [`Propagation::Consume`] skips native handling and the `after_native` handlers
+    pub fn before_native<P: Into<Propagation>>(
+        &mut self,
+        ev: Event,
+        cb: impl FnMut(&mut T, &EventInfo) -> P + Send + 'static,
+    ) -> HandlerId {
+        self.insert_handler(Phase::BeforeNative, ev, cb)
+    }
+
+    /// What the widget should do on a custom event, after the widget's native handling,
+    /// see [`Listener::after_native`](crate::Listener::after_native)
+    pub fn after_native<P: Into<Propagation>>(
+        &mut self,
+        ev: Event,
+        cb: impl FnMut(&mut T, &EventInfo) -> P + Send + 'static,
+    ) -> HandlerId {
+        self.insert_handler(Phase::AfterNative, ev, cb)
+    }
+
+    fn insert_handler<P: Into<Propagation>>(
+        &mut self,
+        phase: Phase,
+        ev: Event,
+        mut cb: impl FnMut(&mut T, &EventInfo) -> P + Send + 'static,
+    ) -> HandlerId {
+        let cb: HandlerFn<T> = Box::new(move |w, info| cb(w, info).into());
+        let id = self
+            .trig
+            .handlers
+            .lock()
+            .unwrap()
+            .insert(phase, &[ev], Arc::new(Mutex::new(cb)));
+        self.sync_native_order();
+        id
+    }
+
+    /// Let FLTK run native handling ahead of the listener, unless `before_native` handlers must run first
+    fn sync_native_order(&mut self) {
+        let native_first = !self
+            .trig
+            .handlers
+            .lock()
+            .unwrap()
+            .has_phase(Phase::BeforeNative);
+        self.wid.super_handle_first(native_first);
+    }
+
+    /// Start a future on every occurrence of an event, after the widget's native handling.
+    /// The futures run concurrently, see [`AsyncListener::on_async_with`] for other policies
+    pub fn on_async<Fut: Future<Output = ()> + Send + 'static>(
+        &mut self,
+        ev: Event,
+        f: impl FnMut(&mut T, EventInfo) -> Fut + Send + 'static,
+    ) -> HandlerId {
+        self.on_async_with(ev, Overlap::Concurrent, f)
+    }
+
+    /// Start a future on every occurrence of an event, after the widget's native handling.
+    /// `overlap` decides what happens to a future still running when the event occurs again
+    /// # Panics
+    /// If no spawner is set, see [`AsyncListener::set_spawner`]
+    pub fn on_async_with<Fut: Future<Output = ()> + Send + 'static>(
+        &mut self,
+        ev: Event,
+        overlap: Overlap,
+        mut f: impl FnMut(&mut T, EventInfo) -> Fut + Send + 'static,
+    ) -> HandlerId {
+        assert!(
+            self.trig.spawner.lock().unwrap().is_some(),
+            "AsyncListener::on_async needs a spawner, enable a runtime feature or call set_spawner"
+        );
+        let runner = Runner::from(overlap);
+        let spawner = self.trig.spawner.clone();
+        self.on(ev, move |w, info| {
+            let fut = f(w, info.clone());
+            // not holding the lock while spawning, which may panic
+            let spawner = spawner.lock().unwrap().clone();
+            if let Some(spawner) = spawner {
+                runner.run(&*spawner, Box::pin(fut));
+            }
+        })
+    }
+
+    /// Unregister a handler, returns false if it wasn't registered
+    pub fn off(&mut self, id: HandlerId) -> bool {
+        let removed = self.trig.handlers.lock().unwrap().remove(id);
+        self.sync_native_order();
+        removed
+    }
+
+    /// Unregister all the handlers of an event, returns how many were removed
+    pub fn off_all(&mut self, ev: Event) -> usize {
+        let removed = self.trig.handlers.lock().unwrap().remove_event(ev);
+        self.sync_native_order();
+        removed
+    }
+
+    /// Unregister all handlers
+    pub fn clear(&mut self) {
+        self.trig.handlers.lock().unwrap().clear();
+        self.sync_native_order();
+    }
+
+    on_events!(impl FnMut(&mut T, &EventInfo) -> P + Send + 'static);
 }
@@ -1,222 +1,506 @@
 use crate::base::BaseListener;
+use crate::chain::Previous;
+use crate::handlers::{
+    dispatch, on_events, run_detached, Handler, HandlerId, Handlers, Phase, Propagation,
+};
+use crate::history::{EventCursor, History};
+use crate::info::{EventInfo, TriggerInfo};
+use crate::keys::Chords;
+use crate::queue::{EventQueue, Overflow, TriggerLog};
+use crate::rate::{Limiter, Rate};
+use fltk::app;
 use fltk::enums::Event;
 use fltk::prelude::{WidgetBase, WidgetExt};
 use std::cell::{Cell, RefCell};
-use std::collections::HashMap;
 use std::rc::Rc;
+use std::task::Waker;
+use std::time::Duration;
 
-type EventMap<T> = HashMap<i32, Option<Box<dyn FnMut(&mut T)>>>;
+type HandlerFn<T> = Box<dyn FnMut(&mut T, &EventInfo) -> Propagation>;
+type SharedHandler<T> = Rc<RefCell<HandlerFn<T>>>;
+type TriggerFn<T> = Box<dyn FnMut(&mut T, &TriggerInfo)>;
+type TriggerHandlers<T> = Rc<RefCell<Vec<Rc<RefCell<TriggerFn<T>>>>>>;
+
+impl<T> Handler<T> for SharedHandler<T> {
+    fn call(&self, w: &mut T, info: &EventInfo) -> Option<Propagation> {
+        self.try_borrow_mut().ok().map(|mut cb| cb(w, info))
+    }
+}
+
+/// What a rate limited listener holds back
+enum Limited {
+    Trigger(TriggerInfo),
+    Event(EventInfo),
+}
+
+/// Rate limits triggers under `None` and events under their kind
+type RateLimiter = Limiter<Option<Event>, Limited>;
+
+/// Run the `on_trigger` callbacks, record the trigger for polling then wake the tasks waiting for one
+fn deliver_trigger<T>(
+    w: &mut T,
+    info: TriggerInfo,
+    triggers: &RefCell<TriggerLog>,
+    handlers: &TriggerHandlers<T>,
+    wakers: &RefCell<Vec<Waker>>,
+) {
+    let handlers = handlers.borrow().clone();
+    for cb in handlers {
+        if let Ok(mut cb) = cb.try_borrow_mut() {
+            cb(w, &info);
+        }
+    }
+    triggers.borrow_mut().push(info);
+    for waker in std::mem::take(&mut *wakers.borrow_mut()) {
+        waker.wake();
+    }
+}
 
 #[derive(Clone)]
 pub struct Trig<T> {
-    triggered: Rc<Cell<bool>>,
-    event: Rc<Cell<Event>>,
-    events: Rc<RefCell<EventMap<T>>>,
+    triggers: Rc<RefCell<TriggerLog>>,
+    trigger_handlers: TriggerHandlers<T>,
+    trigger_wakers: Rc<RefCell<Vec<Waker>>>,
+    queue: Rc<RefCell<EventQueue>>,
+    queue_handled: Rc<Cell<bool>>,
+    history: Rc<RefCell<History>>,
+    handlers: Rc<RefCell<Handlers<SharedHandler<T>>>>,
+    limiter: Rc<RefCell<Option<RateLimiter>>>,
+    pub(crate) chords: Rc<RefCell<Chords<T>>>,
+    previous: Previous<T>,
 }
 
+impl<T> crate::value::sealed::ValueSet<T> for Trig<T> {}
+
 /// The blocking widget listener recieves both `triggered: bool` from [`Listener<T>::triggered()`],
-/// and [`Event`] from [`Listener<T>::event()`].
+/// and the queued [`Event`]s from [`Listener<T>::event()`] or [`Listener<T>::events()`].
 pub type Listener<T> = BaseListener<T, Trig<T>>;
 
 /// core constructor
 impl<T: WidgetBase + WidgetExt + 'static> From<T> for Listener<T> {
-    fn from(mut wid: T) -> Self {
-        let triggered = Rc::new(Cell::new(false));
+    fn from(wid: T) -> Self {
+        Self::listen(wid, Previous::default())
+    }
+}
+
+impl<T: WidgetBase + WidgetExt + 'static> Listener<T> {
+    /// Wrap a widget while keeping the callback and handler it already had,
+    /// like ones set by a custom widget or fl2rust-generated code.
+    /// The previous callback runs before the listener records the trigger,
+    /// the previous handler runs after the widget's native handling as it did before,
+    /// ahead of the listener's `after_native` handlers.
+    /// While the listener has `before_native` handlers, events without `after_native` handlers
+    /// reach the previous handler ahead of native handling.
+    /// # Safety
+    /// The widget's callback and handler, if any, must have been set through `T::set_callback` and `T::handle`,
+    /// and not through another widget type wrapping the same FLTK widget
+    pub unsafe fn chained(wid: T) -> Self {
+        let previous = Previous::take(&wid);
+        Self::listen(wid, previous)
+    }
+
+    fn listen(mut wid: T, previous: Previous<T>) -> Self {
+        let triggers: Rc<RefCell<TriggerLog>> = Rc::default();
+        let trigger_handlers: TriggerHandlers<T> = Rc::default();
+        let trigger_wakers: Rc<RefCell<Vec<Waker>>> = Rc::default();
+        let limiter: Rc<RefCell<Option<RateLimiter>>> = Rc::default();
         wid.set_callback({
-            let triggered = triggered.clone();
-            move |_| {
-                triggered.set(true);
+            let triggers = triggers.clone();
+            let trigger_handlers = trigger_handlers.clone();
+            let trigger_wakers = trigger_wakers.clone();
+            let limiter = limiter.clone();
+            let previous = previous.clone();
+            move |w| {
+                previous.callback(w);
+                let info = TriggerInfo::capture();
+                let limiter = limiter.borrow().clone();
+                match limiter {
+                    Some(limiter) => limiter.submit(None, Limited::Trigger(info)),
+                    None => deliver_trigger(w, info, &triggers, &trigger_handlers, &trigger_wakers),
+                }
             }
         });
-        let event = Rc::new(Cell::new(Event::NoEvent));
-        let events: EventMap<T> = HashMap::new();
-        let events = Rc::from(RefCell::from(events));
+        let queue = Rc::new(RefCell::new(EventQueue::default()));
+        let queue_handled = Rc::new(Cell::new(true));
+        let history: Rc<RefCell<History>> = Rc::default();
+        let handlers: Rc<RefCell<Handlers<SharedHandler<T>>>> = Rc::default();
+        let is_window = wid.as_window().is_some();
+        // FLTK runs native handling ahead of the listener until `before_native` handlers are registered
+        wid.super_handle_first(true);
+        let native_pending = Cell::new(None);
         wid.handle({
-            let event = event.clone();
-            let events = events.clone();
+            let queue = queue.clone();
+            let queue_handled = queue_handled.clone();
+            let history = history.clone();
+            let handlers = handlers.clone();
+            let limiter = limiter.clone();
+            let previous = previous.clone();
             move |w, evt| {
-                let ret = if !events.borrow().is_empty() {
-                    if let Some(Some(cb)) = events.borrow_mut().get_mut(&(evt.bits())) {
-                        cb(w);
-                        w.redraw();
-                        true
-                    } else {
-                        false
-                    }
+                // re-entered by `dispatch` to run native handling, see there
+                if native_pending.get() == Some(evt) {
+                    native_pending.set(None);
+                    return false;
+                }
+                let info = EventInfo::capture(evt, (!is_window).then(|| (w.x(), w.y())));
+                history.borrow_mut().push(&info);
+                let (native_ran, before, after) = {
+                    let handlers = handlers.borrow();
+                    let (before, after) = handlers.get_phases(evt);
+                    (!handlers.has_phase(Phase::BeforeNative), before, after)
+                };
+                let limiter = if after.is_empty() {
+                    None
                 } else {
-                    event.set(evt);
-                    false
+                    limiter.borrow().clone()
+                };
+                let dispatched = match limiter {
+                    // the `after_native` handlers are run by the limiter once FLTK ran native handling
+                    Some(limiter) => {
+                        let dispatched = dispatch(
+                            w,
+                            &info,
+                            native_ran,
+                            before,
+                            &previous,
+                            Vec::new(),
+                            &native_pending,
+                        );
+                        if dispatched.native {
+                            limiter.submit(Some(evt), Limited::Event(info.clone()));
+                        }
+                        dispatched
+                    }
+                    None => dispatch(
+                        w,
+                        &info,
+                        native_ran,
+                        before,
+                        &previous,
+                        after,
+                        &native_pending,
+                    ),
                 };
-                ret
+                if !dispatched.consumed || queue_handled.get() {
+                    queue.borrow_mut().push(info);
+                }
+                dispatched.handled
             }
         });
         let trig = Trig {
-            triggered,
-            event,
-            events,
+            triggers,
+            trigger_handlers,
+            trigger_wakers,
+            queue,
+            queue_handled,
+            history,
+            handlers,
+            limiter,
+            chords: Rc::default(),
+            previous,
         };
         Self { wid, trig }
     }
+
+    /// Only let the last trigger and the last event of each kind of a burst through, once nothing happened for `delay`.
+    /// Applies to polling triggers, the `on_trigger` callbacks and the `on_*`/`after_native` handlers,
+    /// which then run from a timeout with the event state of the last event.
+    /// They run after the event was handled, so the [`Propagation`] they return can't consume it.
+    /// A search input can then react once typing pauses
+    pub fn debounced(mut self, delay: Duration) -> Self
+    where
+        T: Clone,
+    {
+        self.limit(Rate::Debounce(delay));
+        self
+    }
+
+    /// Let triggers and each kind of event through at most once per `interval`, the first of a burst
+    /// from the next event loop iteration and the latest of the rest at the end of the interval.
+    /// Applies like [`Listener::debounced`], an `on_drag` handler then runs at most once per `interval`
+    pub fn throttled(mut self, interval: Duration) -> Self
+    where
+        T: Clone,
+    {
+        self.limit(Rate::Throttle(interval));
+        self
+    }
+
+    fn limit(&mut self, rate: Rate)
+    where
+        T: Clone,
+    {
+        let wid = self.wid.clone();
+        let triggers = self.trig.triggers.clone();
+        let trigger_handlers = self.trig.trigger_handlers.clone();
+        let trigger_wakers = self.trig.trigger_wakers.clone();
+        let handlers = self.trig.handlers.clone();
+        let limiter = Limiter::new(rate, move |item| {
+            let mut w = wid.clone();
+            if w.was_deleted() {
+                return;
+            }
+            match item {
+                Limited::Trigger(info) => {
+                    deliver_trigger(&mut w, info, &triggers, &trigger_handlers, &trigger_wakers)
+                }
+                Limited::Event(info) => {
+                    let after = handlers.borrow().get(Phase::AfterNative, info.event);
+                    run_detached(&mut w, &info, after);
+                }
+            }
+        });
+        *self.trig.limiter.borrow_mut() = Some(limiter);
+    }
+
+    /// Detach the listener and get back the plain widget.
+    /// A chained widget gets its previous callback and handler back,
+    /// clones of the listener stop receiving triggers and events
+    pub fn into_inner(self) -> T {
+        let Self { mut wid, trig } = self;
+        trig.previous.restore(&mut wid);
+        wid.super_handle_first(true);
+        wid
+    }
 }
 
 /// core implementation
 impl<T: WidgetBase + WidgetExt> Listener<T> {
-    /// Check whether a widget was triggered
+    /// Check whether a widget was triggered since the last poll, and reset it.
+    /// The trigger records are kept, poll them with `while let Some(info) = w.trigger_info()`
     pub fn triggered(&self) -> bool {
-        self.trig.triggered.replace(false)
+        self.trigger_count() > 0
     }
 
-    /// Get an event the widget received,
-    /// returns [`Event::NoEvent`] if no events received
-    pub fn event(&self) -> Event {
-        self.trig.event.replace(Event::NoEvent)
+    /// Check whether a widget was triggered, without resetting it
+    pub fn peek_triggered(&self) -> bool {
+        self.trig.triggers.borrow().count() > 0
     }
 
-    /// What the widget should do on a custom event
-    pub fn on(&mut self, ev: Event, cb: impl FnMut(&mut T) + 'static) {
-        self.trig
-            .events
-            .borrow_mut()
-            .insert(ev.bits(), Some(Box::new(cb)));
+    /// Get the number of times the widget was triggered since the last poll, and reset it.
+    /// The trigger records are kept for [`trigger_info`](Self::trigger_info)
+    pub fn trigger_count(&self) -> usize {
+        self.trig.triggers.borrow_mut().take_count()
     }
 
-    /// What the widget should do on hover
-    pub fn on_hover(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::Enter, cb);
+    /// Take the oldest trigger record along with why it happened, whether or not `triggered` was polled,
+    /// returns `None` once all the records were taken
+    pub fn trigger_info(&self) -> Option<TriggerInfo> {
+        self.trig.triggers.borrow_mut().pop()
     }
 
-    /// What the widget should do on leave
-    pub fn on_leave(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::Leave, cb);
+    /// Wake the task on the next trigger
+    pub(crate) fn register_trigger_waker(&self, waker: &Waker) {
+        let mut wakers = self.trig.trigger_wakers.borrow_mut();
+        if !wakers.iter().any(|w| w.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
     }
 
-    /// What the widget should do on click
-    pub fn on_click(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::Push, cb);
+    /// Drain the triggers since the last poll, one record per callback invocation, oldest first.
+    /// Only the latest [`DEFAULT_QUEUE_CAPACITY`](crate::DEFAULT_QUEUE_CAPACITY) records are kept
+    pub fn triggers(&self) -> impl Iterator<Item = TriggerInfo> {
+        self.trig.triggers.borrow_mut().take().into_iter()
     }
 
-    /// What the widget should do on release
-    pub fn on_release(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::Released, cb);
+    /// Get the next event the widget received,
+    /// returns [`Event::NoEvent`] if no events are queued
+    pub fn event(&self) -> Event {
+        self.event_info().map_or(Event::NoEvent, |info| info.event)
+    }
+
+    /// Get the next event the widget received along with the event state at the time,
+    /// returns `None` if no events are queued
+    pub fn event_info(&self) -> Option<EventInfo> {
+        self.trig.queue.borrow_mut().pop()
     }
 
-    /// What the widget should do on drag
-    pub fn on_drag(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::Drag, cb);
+    /// Get the next event the widget received without removing it from the queue,
+    /// returns [`Event::NoEvent`] if no events are queued
+    pub fn peek_event(&self) -> Event {
+        self.trig
+            .queue
+            .borrow()
+            .peek()
+            .map_or(Event::NoEvent, |info| info.event)
     }
 
-    /// What the widget should do on focus
-    pub fn on_focus(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::Focus, cb);
+    /// Get a cursor over the events the widget receives from now on.
+    /// Each cursor reads at its own pace, independently of the listener's queue and other cursors
+    pub fn subscribe(&self) -> EventCursor {
+        History::subscribe(&self.trig.history)
     }
 
-    /// What the widget should do on unfocus
-    pub fn on_unfocus(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::Unfocus, cb);
+    /// Drain the events the widget received since the last poll, oldest first
+    pub fn events(&self) -> impl Iterator<Item = Event> + '_ {
+        self.event_infos().map(|info| info.event)
     }
 
-    /// What the widget should do on keydown
-    pub fn on_keydown(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::KeyDown, cb);
+    /// Drain the events the widget received since the last poll along with their event state, oldest first
+    pub fn event_infos(&self) -> impl Iterator<Item = EventInfo> + '_ {
+        std::iter::from_fn(move || self.event_info())
     }
 
-    /// What the widget should do on keyup
-    pub fn on_keyup(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::KeyUp, cb);
+    /// Number of events waiting to be polled
+    pub fn queued_events(&self) -> usize {
+        self.trig.queue.borrow().len()
     }
 
-    /// What the widget should do on close
-    pub fn on_close(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::Close, cb);
+    /// Set how many events are kept between polls, see [`DEFAULT_QUEUE_CAPACITY`](crate::DEFAULT_QUEUE_CAPACITY).
+    /// Shrinking the queue discards the oldest events
+    pub fn set_queue_capacity(&mut self, capacity: usize) {
+        self.trig.queue.borrow_mut().set_capacity(capacity);
     }
 
-    /// What the widget should do on move
-    pub fn on_move(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::Move, cb);
+    /// Set what happens when an event arrives and the queue is full
+    pub fn set_overflow(&mut self, overflow: Overflow) {
+        self.trig.queue.borrow_mut().set_overflow(overflow);
     }
 
-    /// What the widget should do on shortcut
-    pub fn on_shortcut(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::Shortcut, cb);
+    /// Set whether events a handler consumed, see [`Propagation`], are still queued for polling, defaults to true
+    pub fn set_queue_handled(&mut self, flag: bool) {
+        self.trig.queue_handled.set(flag);
     }
 
-    /// What the widget should do on deactivate
-    pub fn on_deactivate(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::Deactivate, cb);
+    /// Initialize with a queue capacity and overflow policy
+    pub fn with_queue(mut self, capacity: usize, overflow: Overflow) -> Self {
+        self.set_queue_capacity(capacity);
+        self.set_overflow(overflow);
+        self
     }
 
-    /// What the widget should do on activate
-    pub fn on_activate(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::Activate, cb);
+    /// What the widget should do on a custom event, after the widget's native handling.
+    /// Handlers stack, they run in registration order
+    pub fn on<P: Into<Propagation>>(
+        &mut self,
+        ev: Event,
+        cb: impl FnMut(&mut T, &EventInfo) -> P + 'static,
+    ) -> HandlerId {
+        self.after_native(ev, cb)
     }
 
-    /// What the widget should do on hide
-    pub fn on_hide(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::Hide, cb);
+    /// What the widget should do on a custom event, before the widget's native handling.
+    /// Returning [`Propagation::Consume`] skips native handling and the `after_native` handlers
+    pub fn before_native<P: Into<Propagation>>(
+        &mut self,
+        ev: Event,
+        cb: impl FnMut(&mut T, &EventInfo) -> P + 'static,
+    ) -> HandlerId {
+        self.insert_handler(Phase::BeforeNative, &[ev], cb)
     }
 
-    /// What the widget should do on show
-    pub fn on_show(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::Show, cb);
+    /// What the widget should do on a custom event, after the widget's native handling.
+    /// While the widget has `before_native` handlers, native handling is invoked from the listener,
+    /// if neither it nor a handler handles the event FLTK runs native handling once more
+    pub fn after_native<P: Into<Propagation>>(
+        &mut self,
+        ev: Event,
+        cb: impl FnMut(&mut T, &EventInfo) -> P + 'static,
+    ) -> HandlerId {
+        self.insert_handler(Phase::AfterNative, &[ev], cb)
     }
 
-    /// What the widget should do on paste
-    pub fn on_paste(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::Paste, cb);
+    /// One handler for several events after the widget's native handling, like the steps of a gesture
+    pub(crate) fn on_many<P: Into<Propagation>>(
+        &mut self,
+        events: &[Event],
+        cb: impl FnMut(&mut T, &EventInfo) -> P + 'static,
+    ) -> HandlerId {
+        self.insert_handler(Phase::AfterNative, events, cb)
     }
 
-    /// What the widget should do on selection_clear
-    pub fn on_selection_clear(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::SelectionClear, cb);
+    /// One handler for several events before the widget's native handling
+    pub(crate) fn before_native_many<P: Into<Propagation>>(
+        &mut self,
+        events: &[Event],
+        cb: impl FnMut(&mut T, &EventInfo) -> P + 'static,
+    ) -> HandlerId {
+        self.insert_handler(Phase::BeforeNative, events, cb)
     }
 
-    /// What the widget should do on mousewheel
-    pub fn on_mousewheel(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::MouseWheel, cb);
+    fn insert_handler<P: Into<Propagation>>(
+        &mut self,
+        phase: Phase,
+        events: &[Event],
+        mut cb: impl FnMut(&mut T, &EventInfo) -> P + 'static,
+    ) -> HandlerId {
+        let cb: HandlerFn<T> = Box::new(move |w, info| cb(w, info).into());
+        let id = self
+            .trig
+            .handlers
+            .borrow_mut()
+            .insert(phase, events, Rc::new(RefCell::new(cb)));
+        self.sync_native_order();
+        id
     }
 
-    /// What the widget should do on dnd_enter
-    pub fn on_dnd_enter(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::DndEnter, cb);
+    /// Let FLTK run native handling ahead of the listener, unless `before_native` handlers must run first
+    fn sync_native_order(&mut self) {
+        let native_first = !self.trig.handlers.borrow().has_phase(Phase::BeforeNative);
+        self.wid.super_handle_first(native_first);
     }
 
-    /// What the widget should do on dnd_drag
-    pub fn on_dnd_drag(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::DndDrag, cb);
+    /// Unregister a handler, returns false if it wasn't registered
+    pub fn off(&mut self, id: HandlerId) -> bool {
+        let removed =
+            self.trig.handlers.borrow_mut().remove(id) || self.trig.chords.borrow_mut().remove(id);
+        self.sync_native_order();
+        removed
     }
 
-    /// What the widget should do on dnd_leave
-    pub fn on_dnd_leave(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::DndLeave, cb);
+    /// Unregister all the handlers of an event, returns how many were removed.
+    /// A handler registered for several events, like [`Listener::on_key`](crate::Listener::on_key), is removed for all of them
+    pub fn off_all(&mut self, ev: Event) -> usize {
+        let removed = self.trig.handlers.borrow_mut().remove_event(ev);
+        self.sync_native_order();
+        removed
     }
 
-    /// What the widget should do on dnd_release
-    pub fn on_dnd_release(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::DndRelease, cb);
+    /// Unregister all handlers
+    pub fn clear(&mut self) {
+        self.trig.handlers.borrow_mut().clear();
+        self.trig.chords.borrow_mut().clear();
+        self.sync_native_order();
     }
 
-    /// What the widget should do on screen_config_changed
-    pub fn on_screen_config_changed(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::ScreenConfigChanged, cb);
+    /// Reserve an id for a handler kept outside the event handler registry
+    pub(crate) fn reserve_handler_id(&mut self) -> HandlerId {
+        self.trig.handlers.borrow_mut().reserve_id()
     }
 
-    /// What the widget should do on fullscreen
-    pub fn on_fullscreen(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::Fullscreen, cb);
+    /// Whether a handler is registered
+    pub(crate) fn has_handler(&self, id: HandlerId) -> bool {
+        self.trig.handlers.borrow().contains(id)
     }
 
-    /// What the widget should do on zoom_gesture
-    pub fn on_zoom_gesture(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::ZoomGesture, cb);
+    /// What the widget should do when triggered, runs from the widget's callback.
+    /// Callbacks stack, they run in registration order and the trigger is still recorded for polling
+    pub fn on_trigger(&mut self, cb: impl FnMut(&mut T, &TriggerInfo) + 'static) {
+        let cb: TriggerFn<T> = Box::new(cb);
+        self.trig
+            .trigger_handlers
+            .borrow_mut()
+            .push(Rc::new(RefCell::new(cb)));
     }
 
-    /// What the widget should do on zoom
-    pub fn on_zoom(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::ZoomEvent, cb);
+    /// Send a message through an [`app::channel`](fltk::app::channel) whenever the widget is triggered
+    pub fn emit_on_trigger<Msg: Clone + Send + Sync + 'static>(
+        &mut self,
+        sender: app::Sender<Msg>,
+        msg: Msg,
+    ) {
+        self.on_trigger(move |_, _| sender.send(msg.clone()));
     }
 
-    /// What the widget should do on resize
-    pub fn on_resize(&mut self, cb: impl FnMut(&mut T) + 'static) {
-        self.on(Event::Resize, cb);
+    /// Send a message through an [`app::channel`](fltk::app::channel) on every occurrence of an event,
+    /// after the widget's native handling
+    pub fn emit_on<Msg: Send + Sync + 'static>(
+        &mut self,
+        ev: Event,
+        sender: app::Sender<Msg>,
+        mut f: impl FnMut(&EventInfo) -> Msg + 'static,
+    ) -> HandlerId {
+        self.on(ev, move |_, info| sender.send(f(info)))
     }
+
+    on_events!(impl FnMut(&mut T, &EventInfo) -> P + 'static);
 }
@@ -0,0 +1,127 @@
+use crate::queue::DEFAULT_QUEUE_CAPACITY;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context, Poll, Waker};
+
+struct SlotState<T> {
+    buf: VecDeque<T>,
+    waker: Option<Waker>,
+    closed: bool,
+}
+
+type Slot<T> = Mutex<SlotState<T>>;
+
+struct Subscribers<T> {
+    slots: Mutex<Vec<Weak<Slot<T>>>>,
+}
+
+/// Once the sending side is gone, receivers drain their buffer then end
+impl<T> Drop for Subscribers<T> {
+    fn drop(&mut self) {
+        let slots = std::mem::take(&mut *self.slots.lock().unwrap());
+        for slot in slots.iter().filter_map(Weak::upgrade) {
+            let mut slot = slot.lock().unwrap();
+            slot.closed = true;
+            if let Some(waker) = slot.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// Sends values to every subscribed [`Receiver`], each of which buffers them until polled.
+pub(crate) struct Broadcast<T> {
+    subs: Arc<Subscribers<T>>,
+}
+
+impl<T> Clone for Broadcast<T> {
+    fn clone(&self) -> Self {
+        Self {
+            subs: self.subs.clone(),
+        }
+    }
+}
+
+impl<T> Default for Broadcast<T> {
+    fn default() -> Self {
+        Self {
+            subs: Arc::new(Subscribers {
+                slots: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for Broadcast<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Broadcast").finish_non_exhaustive()
+    }
+}
+
+impl<T: Clone> Broadcast<T> {
+    pub fn subscribe(&self) -> Receiver<T> {
+        let slot = Arc::new(Mutex::new(SlotState {
+            buf: VecDeque::new(),
+            waker: None,
+            closed: false,
+        }));
+        self.subs.slots.lock().unwrap().push(Arc::downgrade(&slot));
+        Receiver { slot }
+    }
+
+    /// Whether anyone would receive a sent value, so callers can skip building it
+    pub fn has_subscribers(&self) -> bool {
+        self.subs
+            .slots
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|slot| slot.strong_count() > 0)
+    }
+
+    /// Buffer a value for every receiver, the oldest buffered value is dropped once
+    /// a receiver holds [`DEFAULT_QUEUE_CAPACITY`] of them
+    pub fn send(&self, value: T) {
+        let mut slots = self.subs.slots.lock().unwrap();
+        slots.retain(|slot| slot.strong_count() > 0);
+        for slot in slots.iter().filter_map(Weak::upgrade) {
+            let mut slot = slot.lock().unwrap();
+            if slot.buf.len() >= DEFAULT_QUEUE_CAPACITY {
+                slot.buf.pop_front();
+            }
+            slot.buf.push_back(value.clone());
+            if let Some(waker) = slot.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// The receiving end of a [`Broadcast`], dropping it unsubscribes.
+pub(crate) struct Receiver<T> {
+    slot: Arc<Slot<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// The next buffered value, or `None` once the sender is gone and the buffer is drained
+    pub fn poll_recv(&self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut slot = self.slot.lock().unwrap();
+        if let Some(value) = slot.buf.pop_front() {
+            Poll::Ready(Some(value))
+        } else if slot.closed {
+            Poll::Ready(None)
+        } else {
+            slot.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<T> futures_core::Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(self: std::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.poll_recv(cx)
+    }
+}
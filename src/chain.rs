@@ -0,0 +1,83 @@
+use fltk::{
+    enums::Event,
+    prelude::{WidgetBase, WidgetExt},
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+type CallbackFn<T> = dyn FnMut(&mut T);
+type HandleFn<T> = dyn FnMut(&mut T, Event) -> bool;
+type Callback<T> = Rc<RefCell<Box<CallbackFn<T>>>>;
+type Handle<T> = Rc<RefCell<Box<HandleFn<T>>>>;
+
+/// The callback and handler a widget had before a listener wrapped it.
+pub(crate) struct Previous<T> {
+    callback: Option<Callback<T>>,
+    handle: Option<Handle<T>>,
+}
+
+impl<T> Default for Previous<T> {
+    fn default() -> Self {
+        Self {
+            callback: None,
+            handle: None,
+        }
+    }
+}
+
+impl<T> Clone for Previous<T> {
+    fn clone(&self) -> Self {
+        Self {
+            callback: self.callback.clone(),
+            handle: self.handle.clone(),
+        }
+    }
+}
+
+impl<T: WidgetBase + WidgetExt + 'static> Previous<T> {
+    /// Take ownership of the widget's callback and handler, leaving it with neither.
+    /// # Safety
+    /// They must have been set through `T::set_callback` and `T::handle`,
+    /// fltk-rs stores them type-erased and they're recovered as closures over `T`
+    pub unsafe fn take(wid: &T) -> Self {
+        let callback = wid.user_data().map(|cb| {
+            let cb = std::mem::transmute::<*mut dyn FnMut(), *mut CallbackFn<T>>(Box::into_raw(cb));
+            Rc::new(RefCell::new(Box::from_raw(cb)))
+        });
+        let handle = wid.handle_data().map(|cb| {
+            let cb = std::mem::transmute::<*mut dyn FnMut(Event) -> bool, *mut HandleFn<T>>(
+                Box::into_raw(cb),
+            );
+            Rc::new(RefCell::new(Box::from_raw(cb)))
+        });
+        Self { callback, handle }
+    }
+
+    /// Run the previous callback, if any
+    pub fn callback(&self, w: &mut T) {
+        if let Some(Ok(mut cb)) = self.callback.as_ref().map(|cb| cb.try_borrow_mut()) {
+            cb(w);
+        }
+    }
+
+    /// Run the previous handler, if any, returns whether it handled the event
+    pub fn handle(&self, w: &mut T, ev: Event) -> bool {
+        match self.handle.as_ref().map(|cb| cb.try_borrow_mut()) {
+            Some(Ok(mut cb)) => cb(w, ev),
+            _ => false,
+        }
+    }
+
+    /// Give the widget back its previous callback and handler, or no-op ones
+    pub fn restore(self, wid: &mut T) {
+        let Self { callback, handle } = self;
+        match callback {
+            Some(cb) => wid.set_callback(move |w| (cb.borrow_mut())(w)),
+            None => wid.set_callback(|_| ()),
+        }
+        match handle {
+            Some(cb) => wid.handle(move |w, ev| (cb.borrow_mut())(w, ev)),
+            None => wid.handle(|_, _| false),
+        }
+    }
+}
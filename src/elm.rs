@@ -0,0 +1,108 @@
+//! An Elm-style architecture: listeners produce messages, [`Application::update`] applies them to the model
+//! and [`Application::view_update`] brings the widgets up to date.
+//! ```rust,no_run
+//! use fltk::{app, button, frame, prelude::*, window};
+//! use fltk_evented::{elm, Listener};
+//!
+//! #[derive(Clone, Copy)]
+//! enum Msg {
+//!     Inc,
+//!     Dec,
+//! }
+//!
+//! struct Counter {
+//!     count: i32,
+//!     frame: frame::Frame,
+//! }
+//!
+//! impl elm::Application for Counter {
+//!     type Message = Msg;
+//!
+//!     fn update(&mut self, msg: Msg) {
+//!         match msg {
+//!             Msg::Inc => self.count += 1,
+//!             Msg::Dec => self.count -= 1,
+//!         }
+//!     }
+//!
+//!     fn view_update(&mut self) {
+//!         self.frame.set_label(&self.count.to_string());
+//!     }
+//! }
+//!
+//! let app = app::App::default();
+//! let mut wind = window::Window::default().with_size(160, 200);
+//! let inc: Listener<_> = button::Button::new(40, 20, 80, 40, "+").into();
+//! let frame = frame::Frame::new(40, 80, 80, 40, None);
+//! let dec: Listener<_> = button::Button::new(40, 140, 80, 40, "-").into();
+//! wind.end();
+//! wind.show();
+//!
+//! let mut runner = elm::Runner::new(app);
+//! runner.listen(Msg::Inc, &inc);
+//! runner.listen(Msg::Dec, &dec);
+//! runner.run(&mut Counter { count: 0, frame });
+//! ```
+
+use crate::blocking::Listener;
+use crate::message::MessageLoop;
+use fltk::{
+    app,
+    prelude::{WidgetBase, WidgetExt},
+};
+
+/// The model of an app driven by a [`Runner`].
+pub trait Application {
+    /// The messages produced by the app's listeners
+    type Message: Clone + Send + Sync + 'static;
+
+    /// Apply a message to the model
+    fn update(&mut self, msg: Self::Message);
+
+    /// Bring the widgets up to date with the model, runs once at startup then after each batch of messages
+    /// or events, like the `if fltk_evented::event()` block of a polling loop
+    fn view_update(&mut self);
+}
+
+/// Owns the event loop of an [`Application`], see the [module docs](self).
+pub struct Runner<Msg> {
+    messages: MessageLoop<Msg>,
+}
+
+impl<Msg: Clone + Send + Sync + 'static> Runner<Msg> {
+    /// Creates a runner for the app
+    pub fn new(app: app::App) -> Self {
+        Self {
+            messages: MessageLoop::new(app),
+        }
+    }
+
+    /// Produce `msg` each time a listener is triggered
+    pub fn listen<T: WidgetBase + WidgetExt + Clone + 'static>(
+        &mut self,
+        msg: Msg,
+        listener: &Listener<T>,
+    ) {
+        self.messages.insert(msg, listener);
+    }
+
+    /// The sender for messages produced in other ways, like [`Listener::emit_on`] or a background thread
+    pub fn sender(&self) -> app::Sender<Msg> {
+        self.messages.sender()
+    }
+
+    /// Run the app until it exits, updating the model with every message
+    pub fn run<A: Application<Message = Msg>>(self, model: &mut A) {
+        model.view_update();
+        while self.messages.wait() {
+            let mut updated = false;
+            for msg in self.messages.messages() {
+                model.update(msg);
+                updated = true;
+            }
+            if updated || crate::event() {
+                model.view_update();
+            }
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use crate::blocking::Listener;
+use crate::handlers::{HandlerId, Propagation};
+use crate::info::EventInfo;
+use crate::rate::Timeout;
+use fltk::{
+    enums::Event,
+    prelude::{WidgetBase, WidgetExt},
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// How far, in pixels, the mouse may move between a push and a release for them to still make a click.
+pub const CLICK_SLOP: i32 = 4;
+
+/// The events a press gesture follows, hiding or deactivating the widget cancels it
+const PRESS_EVENTS: [Event; 5] = [
+    Event::Push,
+    Event::Drag,
+    Event::Released,
+    Event::Hide,
+    Event::Deactivate,
+];
+
+/// The movement of a drag gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Drag {
+    /// X coordinate where the mouse was pushed, relative to the window
+    pub start_x: i32,
+    /// Y coordinate where the mouse was pushed, relative to the window
+    pub start_y: i32,
+    /// Total horizontal movement since the push
+    pub dx: i32,
+    /// Total vertical movement since the push
+    pub dy: i32,
+}
+
+impl Drag {
+    fn new(push: (i32, i32), info: &EventInfo) -> Self {
+        Self {
+            start_x: push.0,
+            start_y: push.1,
+            dx: info.x - push.0,
+            dy: info.y - push.1,
+        }
+    }
+
+    fn beyond_slop(&self) -> bool {
+        self.dx.abs() > CLICK_SLOP || self.dy.abs() > CLICK_SLOP
+    }
+}
+
+/// The push of a press gesture is consumed so that the widget keeps receiving the drag and release
+fn press_propagation(info: &EventInfo) -> Propagation {
+    match info.event {
+        Event::Push => Propagation::ConsumeNoRedraw,
+        _ => Propagation::Continue,
+    }
+}
+
+/// Gestures recognized from the listener's events, each returns a [`HandlerId`] unregistering all of its steps
+impl<T: WidgetBase + WidgetExt + Clone + 'static> Listener<T> {
+    /// What the widget should do on a double click, on the second push
+    pub fn on_double_click(&mut self, cb: impl FnMut(&mut T, &EventInfo) + 'static) -> HandlerId {
+        self.on_clicks(2, cb)
+    }
+
+    /// What the widget should do on a triple click, on the third push
+    pub fn on_triple_click(&mut self, cb: impl FnMut(&mut T, &EventInfo) + 'static) -> HandlerId {
+        self.on_clicks(3, cb)
+    }
+
+    fn on_clicks(
+        &mut self,
+        clicks: i32,
+        mut cb: impl FnMut(&mut T, &EventInfo) + 'static,
+    ) -> HandlerId {
+        self.on(Event::Push, move |w, info| {
+            if info.clicks == clicks {
+                cb(w, info);
+            }
+        })
+    }
+
+    /// What the widget should do when the mouse is held down for `delay` without moving,
+    /// the callback gets the push event. Releasing, dragging further than [`CLICK_SLOP`],
+    /// or hiding or deactivating the widget cancels it, as does unregistering the handler
+    pub fn on_long_press(
+        &mut self,
+        delay: Duration,
+        cb: impl FnMut(&mut T, &EventInfo) + 'static,
+    ) -> HandlerId {
+        let cb = Rc::new(RefCell::new(cb));
+        let timer = Timeout::default();
+        let mut push = None;
+        self.on_many(&PRESS_EVENTS, move |w, info| {
+            let cancel = match info.event {
+                Event::Push => {
+                    push = Some((info.x, info.y));
+                    let mut w = w.clone();
+                    let info = info.clone();
+                    let cb = cb.clone();
+                    timer.start(delay, move || {
+                        if !w.was_deleted() {
+                            if let Ok(mut cb) = cb.try_borrow_mut() {
+                                cb(&mut w, &info);
+                            }
+                        }
+                    });
+                    false
+                }
+                Event::Drag => push.is_some_and(|push| Drag::new(push, info).beyond_slop()),
+                _ => true,
+            };
+            if cancel {
+                push = None;
+                timer.cancel();
+            }
+            press_propagation(info)
+        })
+    }
+
+    /// What the widget should do when the mouse is pushed then released without moving further than [`CLICK_SLOP`],
+    /// the callback gets the release event
+    pub fn on_click_no_drag(
+        &mut self,
+        mut cb: impl FnMut(&mut T, &EventInfo) + 'static,
+    ) -> HandlerId {
+        let mut push = None;
+        self.on_many(&PRESS_EVENTS, move |w, info| {
+            match info.event {
+                Event::Push => push = Some((info.x, info.y)),
+                Event::Drag => {
+                    if push.is_some_and(|push| Drag::new(push, info).beyond_slop()) {
+                        push = None;
+                    }
+                }
+                Event::Released => {
+                    if push.take().is_some() {
+                        cb(w, info);
+                    }
+                }
+                _ => push = None,
+            }
+            press_propagation(info)
+        })
+    }
+
+    /// What the widget should do when the mouse is dragged further than [`CLICK_SLOP`] after a push,
+    /// the callback gets the drag event that crossed it and the movement so far
+    pub fn on_drag_start(
+        &mut self,
+        mut cb: impl FnMut(&mut T, &EventInfo, Drag) + 'static,
+    ) -> HandlerId {
+        let mut push = None;
+        let mut dragging = false;
+        self.on_many(&PRESS_EVENTS, move |w, info| {
+            match info.event {
+                Event::Push => {
+                    push = Some((info.x, info.y));
+                    dragging = false;
+                }
+                Event::Drag => {
+                    if let Some(push) = push.filter(|_| !dragging) {
+                        let drag = Drag::new(push, info);
+                        if drag.beyond_slop() {
+                            dragging = true;
+                            cb(w, info, drag);
+                        }
+                    }
+                }
+                _ => push = None,
+            }
+            press_propagation(info)
+        })
+    }
+
+    /// What the widget should do when the mouse is released after a drag started, see [`Listener::on_drag_start`],
+    /// the callback gets the release event and the total movement.
+    /// Hiding or deactivating the widget mid-drag cancels it without running the callback
+    pub fn on_drag_end(
+        &mut self,
+        mut cb: impl FnMut(&mut T, &EventInfo, Drag) + 'static,
+    ) -> HandlerId {
+        let mut push = None;
+        let mut dragging = false;
+        self.on_many(&PRESS_EVENTS, move |w, info| {
+            match info.event {
+                Event::Push => {
+                    push = Some((info.x, info.y));
+                    dragging = false;
+                }
+                Event::Drag => {
+                    dragging |= push.is_some_and(|push| Drag::new(push, info).beyond_slop());
+                }
+                Event::Released => {
+                    if let Some(push) = push.take().filter(|_| dragging) {
+                        cb(w, info, Drag::new(push, info));
+                    }
+                    dragging = false;
+                }
+                _ => {
+                    push = None;
+                    dragging = false;
+                }
+            }
+            press_propagation(info)
+        })
+    }
+}
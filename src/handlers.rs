@@ -0,0 +1,317 @@
+use crate::chain::Previous;
+use crate::info::EventInfo;
+use fltk::{
+    enums::Event,
+    prelude::{WidgetBase, WidgetExt},
+};
+use std::cell::Cell;
+use std::collections::HashMap;
+
+/// What happens to an event once a handler ran.
+/// Handlers returning `()` are treated as [`Propagation::Continue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Propagation {
+    /// Let the event through to the widget's native handling, then redraw the widget
+    #[default]
+    Continue,
+    /// Mark the event as handled, skipping native handling if it hasn't run yet, then redraw the widget
+    Consume,
+    /// Mark the event as handled, skipping native handling if it hasn't run yet, without redrawing
+    ConsumeNoRedraw,
+}
+
+impl Propagation {
+    pub(crate) fn consumes(self) -> bool {
+        self != Propagation::Continue
+    }
+
+    pub(crate) fn redraws(self) -> bool {
+        self != Propagation::ConsumeNoRedraw
+    }
+}
+
+impl From<()> for Propagation {
+    fn from(_: ()) -> Self {
+        Propagation::Continue
+    }
+}
+
+/// A shared handle to a registered handler.
+pub(crate) trait Handler<T> {
+    /// Run the handler, returns `None` if it's already running further up the stack,
+    /// i.e. it made its own widget handle an event
+    fn call(&self, w: &mut T, info: &EventInfo) -> Option<Propagation>;
+}
+
+/// The combined result of the handlers that ran for an event.
+#[derive(Debug, Clone, Copy, Default)]
+struct Outcome {
+    consumed: bool,
+    redraw: bool,
+}
+
+impl Outcome {
+    fn run<T, H: Handler<T>>(&mut self, handlers: Vec<H>, w: &mut T, info: &EventInfo) {
+        for handler in handlers {
+            if let Some(propagation) = handler.call(w, info) {
+                self.consumed |= propagation.consumes();
+                self.redraw |= propagation.redraws();
+            }
+        }
+    }
+}
+
+/// What became of an event run through [`dispatch`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Dispatched {
+    /// What the listener's handler returns to FLTK
+    pub handled: bool,
+    /// Whether the event went on to native handling, i.e. no `before` handler consumed it
+    pub native: bool,
+    /// Whether a handler consumed the event
+    pub consumed: bool,
+}
+
+/// Run an event through the `before` handlers, the widget's native handling,
+/// the `previous` handler of a chained listener then the `after` handlers.
+/// The previous handler runs ahead of native handling when it's left to FLTK.
+/// `native_ran` is whether FLTK already ran native handling, as it does ahead of the listener
+/// while the widget has no `before_native` handlers.
+/// Otherwise FLTK runs native handling once the listener returns false, unless handlers must follow it:
+/// it's then run from here and its result is or-ed, the listener's handler must let the event through
+/// when `native_pending` holds it. FLTK runs native handling once more if nothing handled the event.
+///
+/// Native handling can only be reached through the widget's `handle`, which calls the listener's handler again
+/// while the outer call is still running, fltk-rs then holds two `&mut` to the same closure.
+/// This is sound in practice as long as the listener's handler keeps all its state behind `Rc`/`Cell`
+/// and returns at once when it sees `native_pending`, without touching anything the outer call is using
+pub(crate) fn dispatch<T: WidgetBase + WidgetExt + 'static, H: Handler<T>>(
+    w: &mut T,
+    info: &EventInfo,
+    native_ran: bool,
+    before: Vec<H>,
+    previous: &Previous<T>,
+    after: Vec<H>,
+    native_pending: &Cell<Option<Event>>,
+) -> Dispatched {
+    let mut outcome = Outcome::default();
+    outcome.run(before, w, info);
+    let native = !outcome.consumed;
+    let mut handled = outcome.consumed;
+    if native && (native_ran || !after.is_empty()) {
+        if !native_ran {
+            native_pending.set(Some(info.event));
+            handled |= w.handle_event(info.event);
+            native_pending.set(None);
+        }
+        // like FLTK does for a handler running after native handling, its result is or-ed
+        handled |= previous.handle(w, info.event);
+        outcome.run(after, w, info);
+        handled |= outcome.consumed;
+    } else if native {
+        // FLTK runs native handling after the listener, the previous handler can skip it as it would have
+        // with `super_handle_first(false)`
+        handled |= previous.handle(w, info.event);
+    }
+    if outcome.redraw {
+        w.redraw();
+    }
+    Dispatched {
+        handled,
+        native,
+        consumed: outcome.consumed,
+    }
+}
+
+/// Run handlers outside of the widget's event handling, like ones delayed by a rate limit
+pub(crate) fn run_detached<T: WidgetExt, H: Handler<T>>(
+    w: &mut T,
+    info: &EventInfo,
+    handlers: Vec<H>,
+) {
+    let mut outcome = Outcome::default();
+    outcome.run(handlers, w, info);
+    if outcome.redraw {
+        w.redraw();
+    }
+}
+
+/// When a handler runs relative to the widget's native handling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Phase {
+    BeforeNative,
+    AfterNative,
+}
+
+/// Identifies a registered event handler, returned by [`Listener::on`](crate::Listener::on)
+/// and used to unregister it with [`Listener::off`](crate::Listener::off).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HandlerId(u64);
+
+/// Event handlers stacked per phase and event, in registration order.
+/// `H` is a shared handle to the handler so that dispatch doesn't keep the registry borrowed,
+/// handlers can then (un)register other handlers while running.
+#[derive(Debug)]
+pub(crate) struct Handlers<H> {
+    next_id: u64,
+    map: HashMap<(Phase, Event), Vec<(HandlerId, H)>>,
+}
+
+impl<H> Default for Handlers<H> {
+    fn default() -> Self {
+        Self {
+            next_id: 0,
+            map: HashMap::new(),
+        }
+    }
+}
+
+impl<H: Clone> Handlers<H> {
+    /// Register a handler for one or several events, unregistered together through the returned id
+    pub fn insert(&mut self, phase: Phase, events: &[Event], handler: H) -> HandlerId {
+        let id = self.reserve_id();
+        for &ev in events {
+            self.map
+                .entry((phase, ev))
+                .or_default()
+                .push((id, handler.clone()));
+        }
+        id
+    }
+
+    /// Take an id for a handler kept outside the registry
+    pub fn reserve_id(&mut self) -> HandlerId {
+        let id = HandlerId(self.next_id);
+        self.next_id += 1;
+        id
+    }
+
+    pub fn contains(&self, id: HandlerId) -> bool {
+        self.map
+            .values()
+            .any(|handlers| handlers.iter().any(|(hid, _)| *hid == id))
+    }
+
+    /// Whether any handler is registered for a phase
+    pub fn has_phase(&self, phase: Phase) -> bool {
+        self.map.keys().any(|(p, _)| *p == phase)
+    }
+
+    pub fn remove(&mut self, id: HandlerId) -> bool {
+        let mut found = false;
+        self.map.retain(|_, handlers| {
+            handlers.retain(|(hid, _)| {
+                found |= *hid == id;
+                *hid != id
+            });
+            !handlers.is_empty()
+        });
+        found
+    }
+
+    /// Unregister the handlers of an event, along with the other events they were registered for
+    pub fn remove_event(&mut self, ev: Event) -> usize {
+        let ids: Vec<HandlerId> = [Phase::BeforeNative, Phase::AfterNative]
+            .into_iter()
+            .filter_map(|phase| self.map.get(&(phase, ev)))
+            .flat_map(|handlers| handlers.iter().map(|(id, _)| *id))
+            .collect();
+        ids.into_iter().filter(|id| self.remove(*id)).count()
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// The handlers registered for an event, before and after native handling
+    pub fn get_phases(&self, ev: Event) -> (Vec<H>, Vec<H>) {
+        (
+            self.get(Phase::BeforeNative, ev),
+            self.get(Phase::AfterNative, ev),
+        )
+    }
+
+    /// The handlers registered for an event, in registration order
+    pub fn get(&self, phase: Phase, ev: Event) -> Vec<H> {
+        self.map
+            .get(&(phase, ev))
+            .map_or_else(Vec::new, |handlers| {
+                handlers.iter().map(|(_, h)| h.clone()).collect()
+            })
+    }
+}
+
+/// Generates the `on_<event>` shorthands for [`Listener::on`](crate::Listener::on)-like methods,
+/// given the type of the callback they take
+macro_rules! on_events {
+    ($cb:ty) => {
+        $crate::handlers::on_events! {
+            @impl $cb;
+            /// What the widget should do on hover
+            on_hover => Enter,
+            /// What the widget should do on leave
+            on_leave => Leave,
+            /// What the widget should do on click
+            on_click => Push,
+            /// What the widget should do on release
+            on_release => Released,
+            /// What the widget should do on drag
+            on_drag => Drag,
+            /// What the widget should do on focus
+            on_focus => Focus,
+            /// What the widget should do on unfocus
+            on_unfocus => Unfocus,
+            /// What the widget should do on keydown
+            on_keydown => KeyDown,
+            /// What the widget should do on keyup
+            on_keyup => KeyUp,
+            /// What the widget should do on close
+            on_close => Close,
+            /// What the widget should do on move
+            on_move => Move,
+            /// What the widget should do on shortcut
+            on_shortcut => Shortcut,
+            /// What the widget should do on deactivate
+            on_deactivate => Deactivate,
+            /// What the widget should do on activate
+            on_activate => Activate,
+            /// What the widget should do on hide
+            on_hide => Hide,
+            /// What the widget should do on show
+            on_show => Show,
+            /// What the widget should do on paste
+            on_paste => Paste,
+            /// What the widget should do on selection_clear
+            on_selection_clear => SelectionClear,
+            /// What the widget should do on mousewheel
+            on_mousewheel => MouseWheel,
+            /// What the widget should do on dnd_enter
+            on_dnd_enter => DndEnter,
+            /// What the widget should do on dnd_drag
+            on_dnd_drag => DndDrag,
+            /// What the widget should do on dnd_leave
+            on_dnd_leave => DndLeave,
+            /// What the widget should do on dnd_release
+            on_dnd_release => DndRelease,
+            /// What the widget should do on screen_config_changed
+            on_screen_config_changed => ScreenConfigChanged,
+            /// What the widget should do on fullscreen
+            on_fullscreen => Fullscreen,
+            /// What the widget should do on zoom_gesture
+            on_zoom_gesture => ZoomGesture,
+            /// What the widget should do on zoom
+            on_zoom => ZoomEvent,
+            /// What the widget should do on resize
+            on_resize => Resize,
+        }
+    };
+    (@impl $cb:ty; $($(#[$doc:meta])* $name:ident => $ev:ident,)*) => {
+        $(
+            $(#[$doc])*
+            pub fn $name<P: Into<Propagation>>(&mut self, cb: $cb) -> HandlerId {
+                self.on(Event::$ev, cb)
+            }
+        )*
+    };
+}
+pub(crate) use on_events;
@@ -0,0 +1,203 @@
+use crate::info::EventInfo;
+use crate::queue::DEFAULT_QUEUE_CAPACITY;
+use fltk::enums::Event;
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+/// The latest events received by a widget, numbered in arrival order.
+/// Events are only kept while cursors are subscribed.
+#[derive(Debug, Default)]
+pub(crate) struct History {
+    events: VecDeque<(u64, EventInfo)>,
+    next_seq: u64,
+    cursors: usize,
+}
+
+impl History {
+    pub fn push(&mut self, info: &EventInfo) {
+        if self.cursors == 0 {
+            return;
+        }
+        if self.events.len() >= DEFAULT_QUEUE_CAPACITY {
+            self.events.pop_front();
+        }
+        self.events.push_back((self.next_seq, info.clone()));
+        self.next_seq += 1;
+    }
+
+    pub fn subscribe(history: &Rc<RefCell<History>>) -> EventCursor {
+        let mut h = history.borrow_mut();
+        h.cursors += 1;
+        EventCursor {
+            history: history.clone(),
+            pos: Cell::new(h.next_seq),
+        }
+    }
+
+    fn get(&self, pos: u64) -> Option<&(u64, EventInfo)> {
+        self.events.iter().find(|(seq, _)| *seq >= pos)
+    }
+}
+
+/// Reads a listener's events with its own read position, see [`Listener::subscribe`](crate::Listener::subscribe).
+/// Each cursor sees every event received after it was created, whatever other cursors or the listener consume.
+/// A cursor falling more than [`DEFAULT_QUEUE_CAPACITY`] events behind skips the oldest ones.
+#[derive(Debug)]
+pub struct EventCursor {
+    history: Rc<RefCell<History>>,
+    pos: Cell<u64>,
+}
+
+impl Clone for EventCursor {
+    /// The clone starts at the same read position
+    fn clone(&self) -> Self {
+        self.history.borrow_mut().cursors += 1;
+        Self {
+            history: self.history.clone(),
+            pos: self.pos.clone(),
+        }
+    }
+}
+
+impl Drop for EventCursor {
+    fn drop(&mut self) {
+        let mut history = self.history.borrow_mut();
+        history.cursors -= 1;
+        if history.cursors == 0 {
+            history.events.clear();
+        }
+    }
+}
+
+impl EventCursor {
+    /// Get the next event, returns [`Event::NoEvent`] if the cursor is at the latest event
+    pub fn event(&self) -> Event {
+        self.event_info().map_or(Event::NoEvent, |info| info.event)
+    }
+
+    /// Get the next event along with the event state at the time,
+    /// returns `None` if the cursor is at the latest event
+    pub fn event_info(&self) -> Option<EventInfo> {
+        let history = self.history.borrow();
+        let (seq, info) = history.get(self.pos.get())?;
+        self.pos.set(seq + 1);
+        Some(info.clone())
+    }
+
+    /// Get the next event without moving the cursor,
+    /// returns [`Event::NoEvent`] if the cursor is at the latest event
+    pub fn peek_event(&self) -> Event {
+        let history = self.history.borrow();
+        history
+            .get(self.pos.get())
+            .map_or(Event::NoEvent, |(_, info)| info.event)
+    }
+
+    /// Drain the events up to the latest one, oldest first
+    pub fn events(&self) -> impl Iterator<Item = Event> + '_ {
+        self.event_infos().map(|info| info.event)
+    }
+
+    /// Drain the events up to the latest one along with their event state, oldest first
+    pub fn event_infos(&self) -> impl Iterator<Item = EventInfo> + '_ {
+        std::iter::from_fn(move || self.event_info())
+    }
+
+    /// Number of events the cursor hasn't read yet
+    pub fn pending(&self) -> usize {
+        let history = self.history.borrow();
+        let pos = self.pos.get();
+        history.events.iter().filter(|(seq, _)| *seq >= pos).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fltk::enums::{Key, Shortcut};
+    use std::time::Instant;
+
+    fn event(event: Event) -> EventInfo {
+        EventInfo {
+            event,
+            x: 0,
+            y: 0,
+            widget_x: 0,
+            widget_y: 0,
+            key: Key::None,
+            button: 0,
+            state: Shortcut::None,
+            clicks: 1,
+            dx: 0,
+            dy: 0,
+            text: String::new(),
+            timestamp: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn keeps_nothing_without_cursors() {
+        let history = Rc::new(RefCell::new(History::default()));
+        history.borrow_mut().push(&event(Event::Push));
+        let cursor = History::subscribe(&history);
+        assert_eq!(cursor.pending(), 0);
+        assert_eq!(cursor.event(), Event::NoEvent);
+    }
+
+    #[test]
+    fn cursors_read_independently() {
+        let history = Rc::new(RefCell::new(History::default()));
+        let first = History::subscribe(&history);
+        history.borrow_mut().push(&event(Event::Push));
+        let second = History::subscribe(&history);
+        history.borrow_mut().push(&event(Event::Released));
+        assert_eq!(first.pending(), 2);
+        assert_eq!(first.peek_event(), Event::Push);
+        assert_eq!(
+            first.events().collect::<Vec<_>>(),
+            [Event::Push, Event::Released]
+        );
+        assert_eq!(first.event(), Event::NoEvent);
+        // the second cursor only sees what came after it
+        assert_eq!(second.pending(), 1);
+        assert_eq!(second.event(), Event::Released);
+    }
+
+    #[test]
+    fn clones_start_at_the_same_position() {
+        let history = Rc::new(RefCell::new(History::default()));
+        let cursor = History::subscribe(&history);
+        history.borrow_mut().push(&event(Event::Push));
+        history.borrow_mut().push(&event(Event::Released));
+        assert_eq!(cursor.event(), Event::Push);
+        let clone = cursor.clone();
+        assert_eq!(cursor.event(), Event::Released);
+        assert_eq!(clone.event(), Event::Released);
+        assert_eq!(history.borrow().cursors, 2);
+    }
+
+    #[test]
+    fn dropping_the_last_cursor_clears_events() {
+        let history = Rc::new(RefCell::new(History::default()));
+        let cursor = History::subscribe(&history);
+        let clone = cursor.clone();
+        history.borrow_mut().push(&event(Event::Push));
+        drop(cursor);
+        assert_eq!(history.borrow().events.len(), 1);
+        drop(clone);
+        assert!(history.borrow().events.is_empty());
+    }
+
+    #[test]
+    fn lagging_cursors_skip_the_oldest() {
+        let history = Rc::new(RefCell::new(History::default()));
+        let cursor = History::subscribe(&history);
+        history.borrow_mut().push(&event(Event::Push));
+        for _ in 0..DEFAULT_QUEUE_CAPACITY {
+            history.borrow_mut().push(&event(Event::Move));
+        }
+        assert_eq!(cursor.pending(), DEFAULT_QUEUE_CAPACITY);
+        assert_eq!(cursor.event(), Event::Move);
+    }
+}
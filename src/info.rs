@@ -0,0 +1,141 @@
+use fltk::{
+    app::{self, MouseButton},
+    enums::{CallbackReason, Event, Key, Shortcut},
+};
+use std::time::Instant;
+
+/// Keyboard modifiers to match, a combination of [`Shortcut::Shift`], [`Shortcut::Ctrl`], [`Shortcut::Alt`]
+/// and [`Shortcut::Meta`], or [`Shortcut::None`].
+pub type Modifiers = Shortcut;
+
+const MODIFIER_KEYS: Shortcut = Shortcut::Shift
+    .union(Shortcut::Ctrl)
+    .union(Shortcut::Alt)
+    .union(Shortcut::Meta);
+
+/// A snapshot of the event state, taken while the widget was handling the event.
+/// Reading `app::event_*()` later would describe whatever event came last.
+#[derive(Debug, Clone)]
+pub struct EventInfo {
+    /// The event received
+    pub event: Event,
+    /// Mouse x coordinate, relative to the window
+    pub x: i32,
+    /// Mouse y coordinate, relative to the window
+    pub y: i32,
+    /// Mouse x coordinate, relative to the widget
+    pub widget_x: i32,
+    /// Mouse y coordinate, relative to the widget
+    pub widget_y: i32,
+    /// The key pressed or released
+    pub key: Key,
+    /// The last mouse button pushed, 1 for left, 2 for middle, 3 for right
+    pub button: i32,
+    /// The keyboard modifiers and mouse buttons held
+    pub state: Shortcut,
+    /// Number of consecutive clicks, 1 for a single click, 2 for a double click
+    pub clicks: i32,
+    /// Horizontal mousewheel delta, right is positive
+    pub dx: i32,
+    /// Vertical mousewheel delta, down is positive
+    pub dy: i32,
+    /// Text associated with the event, like typed characters or pasted text
+    pub text: String,
+    /// When the event was received
+    pub timestamp: Instant,
+}
+
+impl EventInfo {
+    /// Capture the current event state, `origin` is the widget's position in its window
+    pub(crate) fn capture(event: Event, origin: Option<(i32, i32)>) -> Self {
+        let (x, y) = app::event_coords();
+        let (ox, oy) = origin.unwrap_or((0, 0));
+        Self {
+            event,
+            x,
+            y,
+            widget_x: x - ox,
+            widget_y: y - oy,
+            key: app::event_key(),
+            button: app::event_button(),
+            state: app::event_state(),
+            clicks: app::event_clicks_num() + 1,
+            dx: app::event_dx_value(),
+            dy: app::event_dy_value(),
+            text: app::event_text(),
+            timestamp: Instant::now(),
+        }
+    }
+
+    /// The last mouse button pushed, if it's a known button
+    pub fn mouse_button(&self) -> Option<MouseButton> {
+        mouse_button(self.button)
+    }
+
+    /// The keyboard modifiers held, without the lock keys and mouse buttons of [`EventInfo::state`]
+    pub fn modifiers(&self) -> Modifiers {
+        self.state & MODIFIER_KEYS
+    }
+}
+
+fn mouse_button(button: i32) -> Option<MouseButton> {
+    match button {
+        1 => Some(MouseButton::Left),
+        2 => Some(MouseButton::Middle),
+        3 => Some(MouseButton::Right),
+        4 => Some(MouseButton::Back),
+        5 => Some(MouseButton::Forward),
+        _ => None,
+    }
+}
+
+/// A record of one invocation of the widget's callback, telling why and how the widget was triggered.
+/// An input committed with Enter has [`CallbackReason::Changed`] and [`Key::Enter`],
+/// one committed by losing focus has [`CallbackReason::LostFocus`].
+#[derive(Debug, Clone)]
+pub struct TriggerInfo {
+    /// Why the callback ran, like [`CallbackReason::LostFocus`] for an input
+    pub reason: CallbackReason,
+    /// The event being handled when the callback ran
+    pub event: Event,
+    /// The last key pressed or released, like [`Key::Enter`] for an input committed with Enter
+    pub key: Key,
+    /// The last mouse button pushed, 1 for left, 2 for middle, 3 for right
+    pub button: i32,
+    /// The keyboard modifiers and mouse buttons held
+    pub state: Shortcut,
+    /// When the callback ran
+    pub timestamp: Instant,
+}
+
+impl TriggerInfo {
+    /// Capture the state of the callback being run
+    pub(crate) fn capture() -> Self {
+        Self {
+            reason: app::callback_reason(),
+            event: app::event(),
+            key: app::event_key(),
+            button: app::event_button(),
+            state: app::event_state(),
+            timestamp: Instant::now(),
+        }
+    }
+
+    /// Whether the callback ran while handling a keyboard event, like a shortcut or Enter in an input
+    pub fn from_keyboard(&self) -> bool {
+        matches!(self.event, Event::KeyDown | Event::KeyUp | Event::Shortcut)
+    }
+
+    /// Whether the callback ran while handling a mouse event
+    pub fn from_mouse(&self) -> bool {
+        matches!(
+            self.event,
+            Event::Push | Event::Released | Event::Drag | Event::MouseWheel
+        )
+    }
+
+    /// The last mouse button pushed, if it's a known button
+    pub fn mouse_button(&self) -> Option<MouseButton> {
+        mouse_button(self.button)
+    }
+}
@@ -0,0 +1,728 @@
+use crate::blocking::Listener;
+use crate::handlers::{HandlerId, Propagation};
+use crate::info::{EventInfo, Modifiers};
+use crate::rate::Timeout;
+use fltk::{
+    app,
+    enums::{Event, Key, Shortcut},
+    prelude::{WidgetBase, WidgetExt},
+};
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::rc::Rc;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Named keys of accelerator strings, matched case-insensitively
+const KEY_NAMES: [(&str, Key); 25] = [
+    ("Enter", Key::Enter),
+    ("Return", Key::Enter),
+    ("Escape", Key::Escape),
+    ("Esc", Key::Escape),
+    ("Tab", Key::Tab),
+    ("Backspace", Key::BackSpace),
+    ("Delete", Key::Delete),
+    ("Del", Key::Delete),
+    ("Insert", Key::Insert),
+    ("Home", Key::Home),
+    ("End", Key::End),
+    ("PageUp", Key::PageUp),
+    ("PageDown", Key::PageDown),
+    ("Left", Key::Left),
+    ("Right", Key::Right),
+    ("Up", Key::Up),
+    ("Down", Key::Down),
+    ("Menu", Key::Menu),
+    ("Print", Key::Print),
+    ("Pause", Key::Pause),
+    ("Help", Key::Help),
+    ("ScrollLock", Key::ScrollLock),
+    ("NumLock", Key::NumLock),
+    ("CapsLock", Key::CapsLock),
+    ("KPEnter", Key::KPEnter),
+];
+
+/// Modifier names of accelerator strings, matched case-insensitively
+const MODIFIER_NAMES: [(&str, Shortcut); 9] = [
+    ("Ctrl", Shortcut::Ctrl),
+    ("Control", Shortcut::Ctrl),
+    ("Alt", Shortcut::Alt),
+    ("Option", Shortcut::Alt),
+    ("Shift", Shortcut::Shift),
+    ("Meta", Shortcut::Meta),
+    ("Super", Shortcut::Meta),
+    ("Cmd", Shortcut::Command),
+    ("Command", Shortcut::Command),
+];
+
+/// A key along with the keyboard modifiers held, like `Ctrl+S`.
+/// Parsed from strings like `"Ctrl+Shift+S"`, `"Alt+F4"`, `"Escape"` or `"Ctrl++"`,
+/// `Cmd` stands for [`Shortcut::Command`], Meta on macOS and Ctrl elsewhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Accelerator {
+    /// The key, letters are lowercase like [`EventInfo::key`]
+    pub key: Key,
+    /// The keyboard modifiers held
+    pub modifiers: Modifiers,
+    /// Whether holding the keys down fires again as the key repeats
+    pub repeat: bool,
+}
+
+impl Accelerator {
+    /// Creates an accelerator firing once per key press
+    pub fn new(key: Key, modifiers: Modifiers) -> Self {
+        Self {
+            key,
+            modifiers,
+            repeat: false,
+        }
+    }
+
+    /// Also fire as the key repeats while held down
+    pub fn repeating(mut self) -> Self {
+        self.repeat = true;
+        self
+    }
+
+    /// Whether a key event is of this accelerator, like `Fl::test_shortcut`.
+    /// Ctrl, Alt and Meta must be exactly the ones held. Symbols typed with Shift,
+    /// like `+` which arrives as `=` on US layouts, also match by the character typed, whether Shift is in the accelerator or not
+    pub fn matches(&self, info: &EventInfo) -> bool {
+        let held = info.modifiers();
+        if info.key == self.key && held == self.modifiers {
+            return true;
+        }
+        let exact = Shortcut::Ctrl | Shortcut::Alt | Shortcut::Meta;
+        if held & exact != self.modifiers & exact
+            || !held.contains(self.modifiers & Shortcut::Shift)
+        {
+            return false;
+        }
+        // letters keep the exact Shift state, their text changes with Caps Lock
+        match self.key.to_char().filter(|_| self.key.bits() < 0x7f) {
+            Some(c) if c.is_ascii_graphic() && !c.is_ascii_alphabetic() => info.text.starts_with(c),
+            _ => false,
+        }
+    }
+}
+
+impl From<(Key, Shortcut)> for Accelerator {
+    fn from((key, modifiers): (Key, Shortcut)) -> Self {
+        Self::new(key, modifiers)
+    }
+}
+
+/// # Panics
+/// If the string isn't a valid accelerator, see [`Accelerator::from_str`] to handle the error
+impl From<&str> for Accelerator {
+    fn from(s: &str) -> Self {
+        s.parse().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+/// The error returned when parsing an invalid accelerator string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseAcceleratorError(String);
+
+impl fmt::Display for ParseAcceleratorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid accelerator: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseAcceleratorError {}
+
+impl FromStr for Accelerator {
+    type Err = ParseAcceleratorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseAcceleratorError(format!("{s:?}"));
+        let s = s.trim();
+        // a trailing `+` is the key itself, like in `Ctrl++`
+        let (mods, key) = match s.strip_suffix("++") {
+            Some(mods) => (Some(mods), "+"),
+            None if s == "+" => (None, "+"),
+            None => match s.rsplit_once('+') {
+                Some((mods, key)) => (Some(mods), key),
+                None => (None, s),
+            },
+        };
+        let mut modifiers = Shortcut::None;
+        for name in mods.into_iter().flat_map(|mods| mods.split('+')) {
+            let (_, modifier) = MODIFIER_NAMES
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name.trim()))
+                .ok_or_else(err)?;
+            modifiers |= *modifier;
+        }
+        let key = key.trim();
+        let mut chars = key.chars();
+        let key = match (chars.next(), chars.next()) {
+            (Some(c), None) => Key::from_char(c.to_ascii_lowercase()),
+            _ if key.eq_ignore_ascii_case("Space") => Key::from_char(' '),
+            _ => match KEY_NAMES.iter().find(|(n, _)| n.eq_ignore_ascii_case(key)) {
+                Some((_, key)) => *key,
+                None => key
+                    .strip_prefix(['F', 'f'])
+                    .and_then(|n| n.parse::<i32>().ok())
+                    .filter(|n| (1..=12).contains(n))
+                    .map(Key::fn_key)
+                    .ok_or_else(err)?,
+            },
+        };
+        Ok(Self::new(key, modifiers))
+    }
+}
+
+impl fmt::Display for Accelerator {
+    /// Formats like `Ctrl+Shift+S`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, modifier) in [
+            ("Ctrl", Shortcut::Ctrl),
+            ("Alt", Shortcut::Alt),
+            ("Shift", Shortcut::Shift),
+            ("Meta", Shortcut::Meta),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+        if let Some((name, _)) = KEY_NAMES.iter().find(|(_, key)| *key == self.key) {
+            return f.write_str(name);
+        }
+        if Key::is_fn_key(self.key) {
+            return write!(f, "F{}", self.key.bits() - Key::fn_key(0).bits());
+        }
+        // `to_char` truncates any key to a byte, only printable ones are characters
+        match self.key.to_char().filter(|_| self.key.bits() < 0x7f) {
+            Some(' ') => f.write_str("Space"),
+            Some(c) if c.is_ascii_graphic() => write!(f, "{}", c.to_ascii_uppercase()),
+            _ => write!(f, "{:?}", self.key),
+        }
+    }
+}
+
+/// Key bindings, matched against the key and modifiers captured when the event was received
+impl<T: WidgetBase + WidgetExt> Listener<T> {
+    /// What the widget should do when an accelerator is pressed, like `on_key("Ctrl+S", ..)`
+    /// or `on_key((Key::Enter, Shortcut::Ctrl), ..)`.
+    /// Handlers run before the widget's native handling and consume the key,
+    /// other keys go on to the widget as if the handler wasn't there.
+    /// They fire on key down, and as the key repeats if the accelerator is [`Accelerator::repeating`].
+    /// Keys also reach a widget without focus as [`Event::Shortcut`] when no focused widget handled them
+    /// # Panics
+    /// If `accel` is a string that isn't a valid accelerator
+    pub fn on_key(
+        &mut self,
+        accel: impl Into<Accelerator>,
+        mut cb: impl FnMut(&mut T, &EventInfo) + 'static,
+    ) -> HandlerId {
+        let accel = accel.into();
+        // the key pressed for the accelerator, `=` for `Ctrl++` on US layouts
+        let held: Rc<Cell<Option<Key>>> = Rc::default();
+        let poll = Timeout::default();
+        self.before_native_many(
+            &[
+                Event::KeyDown,
+                Event::Shortcut,
+                Event::KeyUp,
+                Event::Unfocus,
+                Event::Hide,
+            ],
+            move |w, info| {
+                match info.event {
+                    Event::KeyDown | Event::Shortcut => (),
+                    Event::KeyUp => {
+                        held.set(held.get().filter(|key| *key != info.key));
+                        return Propagation::Continue;
+                    }
+                    _ => {
+                        held.set(None);
+                        return Propagation::Continue;
+                    }
+                }
+                // pressing another key stops the repeat
+                if held.get() != Some(info.key) {
+                    held.set(None);
+                }
+                if !accel.matches(info) {
+                    return Propagation::Continue;
+                }
+                let repeat = held.replace(Some(info.key)).is_some();
+                if !repeat {
+                    // a widget without focus doesn't get the key up, FLTK's key state tells when it's released
+                    let held = held.clone();
+                    poll.start_repeating(KEY_UP_POLL, move || {
+                        held.set(held.get().filter(|key| app::event_key_down(*key)));
+                        held.get().is_some()
+                    });
+                }
+                if !repeat || accel.repeat {
+                    cb(w, info);
+                }
+                Propagation::Consume
+            },
+        )
+    }
+}
+
+/// How often a held accelerator checks whether its key was released
+const KEY_UP_POLL: Duration = Duration::from_millis(50);
+
+/// How long a partially typed [`Chord`] waits for its next stroke by default.
+pub const DEFAULT_CHORD_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// A sequence of accelerators pressed one after the other, like `Ctrl+K Ctrl+C`.
+/// Parsed from accelerator strings separated by spaces, see [`Accelerator`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Chord {
+    /// The accelerators, in the order they're pressed
+    pub strokes: Vec<Accelerator>,
+}
+
+impl Chord {
+    fn starts_with(&self, prefix: &Chord) -> bool {
+        self.strokes.len() > prefix.strokes.len() && self.strokes.starts_with(&prefix.strokes)
+    }
+}
+
+impl From<Accelerator> for Chord {
+    fn from(accel: Accelerator) -> Self {
+        vec![accel].into()
+    }
+}
+
+impl From<Vec<Accelerator>> for Chord {
+    /// Repeats of the strokes aren't considered
+    fn from(strokes: Vec<Accelerator>) -> Self {
+        Self {
+            strokes: strokes
+                .into_iter()
+                .map(|accel| Accelerator::new(accel.key, accel.modifiers))
+                .collect(),
+        }
+    }
+}
+
+/// # Panics
+/// If the string isn't a valid chord, see [`Chord::from_str`] to handle the error
+impl From<&str> for Chord {
+    fn from(s: &str) -> Self {
+        s.parse().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl FromStr for Chord {
+    type Err = ParseAcceleratorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let strokes = s
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<Vec<Accelerator>, _>>()?;
+        if strokes.is_empty() {
+            return Err(ParseAcceleratorError(format!("{s:?}")));
+        }
+        Ok(strokes.into())
+    }
+}
+
+impl fmt::Display for Chord {
+    /// Formats like `Ctrl+K Ctrl+C`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, accel) in self.strokes.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{accel}")?;
+        }
+        Ok(())
+    }
+}
+
+type ChordFn<T> = Rc<RefCell<Box<dyn FnMut(&mut T, &EventInfo)>>>;
+type PendingFn = Rc<RefCell<Box<dyn FnMut(Option<&Chord>)>>>;
+
+/// The chords of a listener, dispatched by a single key handler so that they can share prefixes.
+pub(crate) struct Chords<T> {
+    bindings: Vec<(HandlerId, Chord, ChordFn<T>)>,
+    pending_handlers: Vec<PendingFn>,
+    pending: Option<Chord>,
+    /// The chord of the pending strokes, when they also start a longer one, with the event of its last stroke
+    complete: Option<(HandlerId, ChordFn<T>, EventInfo)>,
+    timer: Option<app::TimeoutHandle>,
+    timeout: Duration,
+    dispatcher: Option<HandlerId>,
+}
+
+impl<T> Default for Chords<T> {
+    fn default() -> Self {
+        Self {
+            bindings: Vec::new(),
+            pending_handlers: Vec::new(),
+            pending: None,
+            complete: None,
+            timer: None,
+            timeout: DEFAULT_CHORD_TIMEOUT,
+            dispatcher: None,
+        }
+    }
+}
+
+impl<T> Chords<T> {
+    pub fn remove(&mut self, id: HandlerId) -> bool {
+        let len = self.bindings.len();
+        self.bindings.retain(|(hid, _, _)| *hid != id);
+        if self.complete.as_ref().is_some_and(|(hid, _, _)| *hid == id) {
+            self.complete = None;
+        }
+        self.bindings.len() != len
+    }
+
+    pub fn clear(&mut self) {
+        self.bindings.clear();
+        self.pending_handlers.clear();
+        self.set_pending(None);
+    }
+
+    /// Replace the pending chord, returns the callbacks to notify if it changed
+    fn set_pending(&mut self, pending: Option<Chord>) -> Vec<PendingFn> {
+        self.complete = None;
+        if let Some(handle) = self.timer.take() {
+            app::remove_timeout3(handle);
+        }
+        if self.pending == pending {
+            return Vec::new();
+        }
+        self.pending = pending;
+        self.pending_handlers.clone()
+    }
+}
+
+/// Tell the `on_chord_pending` callbacks about the pending chord
+fn notify(handlers: Vec<PendingFn>, pending: Option<&Chord>) {
+    for cb in handlers {
+        if let Ok(mut cb) = cb.try_borrow_mut() {
+            cb(pending);
+        }
+    }
+}
+
+/// Whether a key is a modifier key, pressed on the way to an accelerator
+fn is_modifier_key(key: Key) -> bool {
+    (Key::ShiftL.bits()..=Key::AltR.bits()).contains(&key.bits())
+}
+
+/// Run a chord's callback with the event of its last stroke
+fn fire<T>(cb: &ChordFn<T>, w: &mut T, info: &EventInfo) {
+    if let Ok(mut cb) = cb.try_borrow_mut() {
+        cb(w, info);
+    }
+}
+
+/// Run a key press through the chords, returns whether it was part of one
+fn dispatch_chord<T: Clone + 'static>(
+    chords: &Rc<RefCell<Chords<T>>>,
+    w: &mut T,
+    info: &EventInfo,
+) -> bool {
+    if is_modifier_key(info.key) {
+        return false;
+    }
+    let mut state = chords.borrow_mut();
+    let pending = state.pending.clone().unwrap_or(Chord {
+        strokes: Vec::new(),
+    });
+    let n = pending.strokes.len();
+    // the chords continuing the pending strokes with this key
+    let next = state
+        .bindings
+        .iter()
+        .filter(|(_, c, _)| c.starts_with(&pending) && c.strokes[n].matches(info));
+    let complete = next
+        .clone()
+        .find(|(_, c, _)| c.strokes.len() == n + 1)
+        .map(|(id, _, cb)| (*id, cb.clone()));
+    let longer = next
+        .filter(|(_, c, _)| c.strokes.len() > n + 1)
+        .map(|(_, c, _)| Chord {
+            strokes: c.strokes[..=n].to_vec(),
+        })
+        .next();
+    if let Some(candidate) = longer {
+        // a chord that also starts a longer one fires once no stroke follows it
+        let handlers = state.set_pending(Some(candidate.clone()));
+        state.complete = complete.map(|(id, cb)| (id, cb, info.clone()));
+        let timeout = state.timeout.as_secs_f64();
+        let chords = chords.clone();
+        let mut w = w.clone();
+        state.timer = Some(app::add_timeout3(timeout, move |_| {
+            let (handlers, complete) = {
+                let mut state = chords.borrow_mut();
+                state.timer = None;
+                let complete = state.complete.take();
+                (state.set_pending(None), complete)
+            };
+            notify(handlers, None);
+            if let Some((_, cb, info)) = complete {
+                fire(&cb, &mut w, &info);
+            }
+        }));
+        drop(state);
+        notify(handlers, Some(&candidate));
+        return true;
+    }
+    let was_pending = state.pending.is_some();
+    let typed = state.complete.take();
+    let handlers = state.set_pending(None);
+    drop(state);
+    notify(handlers, None);
+    if let Some((_, cb)) = complete {
+        fire(&cb, w, info);
+        return true;
+    }
+    if let Some((_, cb, typed)) = typed {
+        // the strokes typed so far were a chord of their own, it fires and the key starts over
+        fire(&cb, w, &typed);
+        return dispatch_chord(chords, w, info);
+    }
+    // an unrelated key cancels the pending chord and is swallowed with it
+    was_pending
+}
+
+/// Multi-stroke key bindings, the strokes of a chord are consumed as they're typed
+impl<T: WidgetBase + WidgetExt + Clone + 'static> Listener<T> {
+    /// What the widget should do when a chord is typed, like `on_chord("Ctrl+K Ctrl+C", ..)`.
+    /// Chords can share a prefix, each stroke has [`Listener::set_chord_timeout`] to follow the previous one.
+    /// A chord that starts a longer one, like `Ctrl+K` with `Ctrl+K Ctrl+C`, fires once the timeout passes
+    /// or a key that doesn't continue it is typed, that key then starts over.
+    /// Otherwise a key that doesn't continue the pending chord cancels it and is consumed,
+    /// keys that don't start a chord go on to the widget
+    /// # Panics
+    /// If `chord` is a string that isn't a valid chord
+    pub fn on_chord(
+        &mut self,
+        chord: impl Into<Chord>,
+        cb: impl FnMut(&mut T, &EventInfo) + 'static,
+    ) -> HandlerId {
+        let chord = chord.into();
+        let dispatcher = self.trig.chords.borrow().dispatcher;
+        if !dispatcher.is_some_and(|id| self.has_handler(id)) {
+            let chords = self.trig.chords.clone();
+            let id = self.before_native_many(&[Event::KeyDown, Event::Shortcut], move |w, info| {
+                match dispatch_chord(&chords, w, info) {
+                    true => Propagation::Consume,
+                    false => Propagation::Continue,
+                }
+            });
+            self.trig.chords.borrow_mut().dispatcher = Some(id);
+        }
+        let id = self.reserve_handler_id();
+        let cb: ChordFn<T> = Rc::new(RefCell::new(Box::new(cb)));
+        self.trig.chords.borrow_mut().bindings.push((id, chord, cb));
+        id
+    }
+
+    /// The strokes typed so far of a chord waiting for its next one, to show in a status bar
+    pub fn pending_chord(&self) -> Option<Chord> {
+        self.trig.chords.borrow().pending.clone()
+    }
+
+    /// What to do when a chord starts, continues, or is completed, cancelled or timed out,
+    /// the callback gets the pending strokes, `None` once there are none
+    pub fn on_chord_pending(&mut self, cb: impl FnMut(Option<&Chord>) + 'static) {
+        let cb: PendingFn = Rc::new(RefCell::new(Box::new(cb)));
+        self.trig.chords.borrow_mut().pending_handlers.push(cb);
+    }
+
+    /// Set how long a partially typed chord waits for its next stroke, see [`DEFAULT_CHORD_TIMEOUT`]
+    pub fn set_chord_timeout(&mut self, timeout: Duration) {
+        self.trig.chords.borrow_mut().timeout = timeout;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Accelerator {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_modifiers_and_keys() {
+        assert_eq!(
+            parse("Ctrl+S"),
+            Accelerator::new(Key::from_char('s'), Shortcut::Ctrl)
+        );
+        assert_eq!(
+            parse("Ctrl+Shift+S"),
+            Accelerator::new(Key::from_char('s'), Shortcut::Ctrl | Shortcut::Shift)
+        );
+        assert_eq!(parse("Alt+F4"), Accelerator::new(Key::F4, Shortcut::Alt));
+        assert_eq!(
+            parse("Escape"),
+            Accelerator::new(Key::Escape, Shortcut::None)
+        );
+        assert_eq!(parse("Esc"), parse("Escape"));
+        assert_eq!(parse(" Ctrl + Enter "), parse("Ctrl+Return"));
+    }
+
+    #[test]
+    fn folds_case() {
+        assert_eq!(parse("ctrl+shift+s"), parse("CTRL+SHIFT+S"));
+        assert_eq!(parse("alt+f4"), parse("Alt+F4"));
+        assert_eq!(parse("pageup"), parse("PageUp"));
+        assert_eq!(parse("S").key, Key::from_char('s'));
+    }
+
+    #[test]
+    fn parses_plus_and_space() {
+        assert_eq!(
+            parse("Ctrl++"),
+            Accelerator::new(Key::from_char('+'), Shortcut::Ctrl)
+        );
+        assert_eq!(
+            parse("+"),
+            Accelerator::new(Key::from_char('+'), Shortcut::None)
+        );
+        assert_eq!(
+            parse("Ctrl+Space"),
+            Accelerator::new(Key::from_char(' '), Shortcut::Ctrl)
+        );
+        assert_eq!(parse("space"), parse("Space"));
+    }
+
+    #[test]
+    fn parses_function_keys() {
+        assert_eq!(parse("F1").key, Key::F1);
+        assert_eq!(parse("f12").key, Key::F12);
+        for n in 1..=12 {
+            assert_eq!(parse(&format!("F{n}")).key, Key::fn_key(n));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid() {
+        for s in [
+            "",
+            "Ctrl+",
+            "Hyper+S",
+            "Ctrl+SS",
+            "F0",
+            "F13",
+            "Fx",
+            "Ctrl+Nope",
+            "S+Ctrl",
+        ] {
+            assert!(s.parse::<Accelerator>().is_err(), "{s:?} parsed");
+        }
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for s in [
+            "Ctrl+S",
+            "Ctrl+Alt+Shift+Meta+K",
+            "Alt+F4",
+            "F12",
+            "Ctrl++",
+            "+",
+            "Space",
+            "Shift+Tab",
+            "Enter",
+            "Ctrl+1",
+            "Menu",
+            "Print",
+            "Pause",
+        ] {
+            let accel = parse(s);
+            assert_eq!(accel.to_string(), s);
+            assert_eq!(parse(&accel.to_string()), accel);
+        }
+        // modifiers are formatted in a fixed order
+        assert_eq!(parse("shift+ctrl+a").to_string(), "Ctrl+Shift+A");
+    }
+
+    #[test]
+    fn displays_unprintable_keys_by_name() {
+        let menu = Accelerator::new(Key::Menu, Shortcut::None);
+        assert_eq!(menu.to_string(), "Menu");
+        let keypad = Accelerator::new(Key::from_i32(Key::KP.bits() + '5' as i32), Shortcut::None);
+        assert!(keypad.to_string().len() > 1);
+    }
+
+    fn key_down(key: char, state: Shortcut, text: &str) -> EventInfo {
+        EventInfo {
+            event: Event::KeyDown,
+            x: 0,
+            y: 0,
+            widget_x: 0,
+            widget_y: 0,
+            key: Key::from_char(key),
+            button: 0,
+            state,
+            clicks: 1,
+            dx: 0,
+            dy: 0,
+            text: text.into(),
+            timestamp: std::time::Instant::now(),
+        }
+    }
+
+    #[test]
+    fn matches_keys_and_modifiers() {
+        let save = parse("Ctrl+S");
+        assert!(save.matches(&key_down('s', Shortcut::Ctrl, "\x13")));
+        // lock keys and mouse buttons don't count
+        assert!(save.matches(&key_down('s', Shortcut::Ctrl | Shortcut::CapsLock, "\x13")));
+        assert!(!save.matches(&key_down('s', Shortcut::Ctrl | Shortcut::Shift, "\x13")));
+        assert!(!save.matches(&key_down('s', Shortcut::None, "s")));
+        // letters don't match by their text
+        assert!(!parse("A").matches(&key_down('a', Shortcut::Shift, "A")));
+    }
+
+    #[test]
+    fn matches_shifted_symbols() {
+        // `+` is typed as Shift+`=` on US layouts
+        let zoom = parse("Ctrl++");
+        let shifted = Shortcut::Ctrl | Shortcut::Shift;
+        assert!(zoom.matches(&key_down('=', shifted, "+")));
+        assert!(zoom.matches(&key_down('+', Shortcut::Ctrl, "+")));
+        assert!(!zoom.matches(&key_down('=', Shortcut::Ctrl, "=")));
+        assert!(!zoom.matches(&key_down('=', shifted | Shortcut::Alt, "+")));
+        assert!(parse("Ctrl+Shift++").matches(&key_down('=', shifted, "+")));
+        assert!(!parse("Ctrl+Shift++").matches(&key_down('+', Shortcut::Ctrl, "+")));
+    }
+
+    #[test]
+    fn parses_chords() {
+        let chord: Chord = "Ctrl+K Ctrl+C".parse().unwrap();
+        assert_eq!(chord.strokes, vec![parse("Ctrl+K"), parse("Ctrl+C")]);
+        assert_eq!(chord.to_string(), "Ctrl+K Ctrl+C");
+        assert_eq!(" ctrl+k   ctrl+c ".parse::<Chord>().unwrap(), chord);
+        assert_eq!(
+            Chord::from("Ctrl++ G"),
+            Chord::from(vec![parse("Ctrl++"), parse("G")])
+        );
+        assert_eq!(Chord::from(parse("Escape")).strokes.len(), 1);
+    }
+
+    #[test]
+    fn rejects_invalid_chords() {
+        for s in ["", "   ", "Ctrl+K Hyper+C", "Ctrl+K Ctrl+"] {
+            assert!(s.parse::<Chord>().is_err(), "{s:?} parsed");
+        }
+    }
+
+    #[test]
+    fn chord_prefixes() {
+        let chord = Chord::from("Ctrl+K Ctrl+C");
+        assert!(chord.starts_with(&Chord::from("Ctrl+K")));
+        assert!(!chord.starts_with(&chord));
+        assert!(!chord.starts_with(&Chord::from("Ctrl+C")));
+        // repeating strokes match plain ones
+        let repeating = Chord::from(vec![parse("Ctrl+K").repeating()]);
+        assert_eq!(repeating, Chord::from("Ctrl+K"));
+    }
+}
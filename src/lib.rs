@@ -3,7 +3,8 @@
 #![doc = include_str!("../README.md")]
 #![cfg_attr(feature = "tokio", doc = concat!(r##"
 ## Async usage
-This crate provides an AsyncListener which can be used in async contexts. This requires enabling either the tokio or async-std features. You can check the examples directory for an example on usage.
+This crate provides an AsyncListener which can be used in async contexts. This requires enabling any of the tokio, async-std, smol or futures features, they can be combined.
+AsyncListener only relies on wakers, so it works on any executor, including `futures::executor`. You can check the examples directory for an example on usage.
 
 ```rust,ignore
 use fltk::{prelude::*, *};
@@ -47,23 +48,109 @@ async fn main() -> Result<(), reqwest::Error> {
 ```
 
 The repo contains an async-std example as well.
+
+Enabling the `futures` feature adds `AsyncListener::events()` and `AsyncListener::triggers()`,
+which return `futures_core::Stream`s usable with `StreamExt` combinators.
+
+AsyncListener has the same `on_*` handlers as Listener, they run on the UI thread and must be `Send`.
+`AsyncListener::on_async()` starts a future on every occurrence of an event:
+```rust,ignore
+let mut btn: AsyncListener<_> = button::Button::default().with_label("Get").into();
+btn.on_async_with(enums::Event::Push, Overlap::CancelPrevious, move |_, _| {
+    let mut buf = buf.clone();
+    async move {
+        let text = reqwest::get("https://www.rust-lang.org").await.unwrap().text().await.unwrap();
+        buf.set_text(&text);
+    }
+});
+```
+The futures are spawned on the runtime of the enabled feature, or through `AsyncListener::set_spawner()`.
 "##))]
 #![allow(clippy::needless_doctest_main)]
 
 mod base;
 pub use base::BaseListener;
 
+mod chain;
+
 mod blocking;
 pub use blocking::Listener;
 
+#[cfg(feature = "elm")]
+pub mod elm;
+
+mod gesture;
+pub use gesture::{Drag, CLICK_SLOP};
+
+mod keys;
+pub use keys::{Accelerator, Chord, ParseAcceleratorError, DEFAULT_CHORD_TIMEOUT};
+
+mod mouse;
+
+mod message;
+pub use message::MessageLoop;
+
+mod set;
+pub use set::ListenerSet;
+
+mod signal;
+pub use signal::{InvalidInput, Signal};
+
+mod value;
+pub use value::{HasValue, ValueListener};
+
+mod history;
+pub use history::EventCursor;
+
+mod handlers;
+pub use handlers::{HandlerId, Propagation};
+
+mod info;
+pub use info::{EventInfo, Modifiers, TriggerInfo};
+
+mod rate;
+
+mod queue;
+pub use queue::{Overflow, DEFAULT_QUEUE_CAPACITY};
+
 pub fn event() -> bool {
     fltk::app::event() != fltk::enums::Event::NoEvent
 }
 
-#[cfg(all(feature = "tokio", feature = "async-std"))]
-compile_error!("Features `tokio` and `async-std` are mutually exclusive.");
-
-#[cfg(any(feature = "tokio", feature = "async-std"))]
+#[cfg(any(
+    feature = "tokio",
+    feature = "async-std",
+    feature = "smol",
+    feature = "futures"
+))]
 mod asynch;
-#[cfg(any(feature = "tokio", feature = "async-std"))]
+#[cfg(feature = "futures")]
+mod broadcast;
+#[cfg(any(
+    feature = "tokio",
+    feature = "async-std",
+    feature = "smol",
+    feature = "futures"
+))]
 pub use asynch::AsyncListener;
+
+#[cfg(any(
+    feature = "tokio",
+    feature = "async-std",
+    feature = "smol",
+    feature = "futures"
+))]
+mod runtime;
+#[cfg(feature = "async-std")]
+pub use runtime::AsyncStd;
+#[cfg(feature = "smol")]
+pub use runtime::Smol;
+#[cfg(feature = "tokio")]
+pub use runtime::Tokio;
+#[cfg(any(
+    feature = "tokio",
+    feature = "async-std",
+    feature = "smol",
+    feature = "futures"
+))]
+pub use runtime::{BoxFuture, Overlap, Spawner};
@@ -0,0 +1,95 @@
+use crate::blocking::Listener;
+use crate::set::ListenerSet;
+use fltk::{
+    app,
+    prelude::{WidgetBase, WidgetExt},
+};
+
+/// Drives an app whose listeners and other code post messages, drained together by [`MessageLoop::messages`].
+/// Listeners either send through [`MessageLoop::sender`] with [`Listener::emit_on_trigger`] and [`Listener::emit_on`],
+/// or are registered with [`MessageLoop::insert`] and polled by the loop.
+/// ```rust,no_run
+/// use fltk::{app, button, enums::Event, prelude::*, window};
+/// use fltk_evented::{Listener, MessageLoop};
+///
+/// #[derive(Clone, Copy)]
+/// enum Msg {
+///     Inc,
+///     Dec,
+///     Hover,
+/// }
+///
+/// let app = app::App::default();
+/// let mut wind = window::Window::default().with_size(400, 300);
+/// let inc: Listener<_> = button::Button::new(160, 50, 80, 35, "+").into();
+/// let mut dec: Listener<_> = button::Button::new(160, 210, 80, 35, "-").into();
+/// wind.end();
+/// wind.show();
+///
+/// let mut ml = MessageLoop::new(app);
+/// ml.insert(Msg::Inc, &inc);
+/// dec.emit_on_trigger(ml.sender(), Msg::Dec);
+/// dec.emit_on(Event::Enter, ml.sender(), |_| Msg::Hover);
+///
+/// let mut count = 0;
+/// ml.run(|msg| match msg {
+///     Msg::Inc => count += 1,
+///     Msg::Dec => count -= 1,
+///     Msg::Hover => println!("{count}"),
+/// });
+/// ```
+pub struct MessageLoop<Msg> {
+    app: app::App,
+    sender: app::Sender<Msg>,
+    receiver: app::Receiver<Msg>,
+    set: ListenerSet<Msg>,
+}
+
+impl<Msg: Clone + Send + Sync + 'static> MessageLoop<Msg> {
+    /// Creates a message loop for the app, with a new [`app::channel`]
+    pub fn new(app: app::App) -> Self {
+        let (sender, receiver) = app::channel();
+        Self {
+            app,
+            sender,
+            receiver,
+            set: ListenerSet::new(),
+        }
+    }
+
+    /// The sending end of the loop's channel
+    pub fn sender(&self) -> app::Sender<Msg> {
+        self.sender
+    }
+
+    /// Poll a listener with the loop, posting `msg` each time it's triggered
+    pub fn insert<T: WidgetBase + WidgetExt + Clone + 'static>(
+        &mut self,
+        msg: Msg,
+        listener: &Listener<T>,
+    ) {
+        self.set.insert(msg, listener);
+    }
+
+    /// Wait for the next events, same as [`app::App::wait`]
+    pub fn wait(&self) -> bool {
+        self.app.wait()
+    }
+
+    /// Drain the pending messages, the triggers of the registered listeners first, then the channel
+    pub fn messages(&self) -> impl Iterator<Item = Msg> + '_ {
+        self.set
+            .poll()
+            .map(|(msg, _)| msg)
+            .chain(std::iter::from_fn(move || self.receiver.recv()))
+    }
+
+    /// Run the app, handling every message until the app exits
+    pub fn run(self, mut f: impl FnMut(Msg)) {
+        while self.wait() {
+            for msg in self.messages() {
+                f(msg);
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::blocking::Listener;
+use crate::handlers::{HandlerId, Propagation};
+use crate::info::{EventInfo, Modifiers};
+use fltk::{
+    app::MouseButton,
+    enums::{Event, Shortcut},
+    prelude::{WidgetBase, WidgetExt},
+};
+
+/// Click handlers matching the mouse button and keyboard modifiers of the push, as captured when it was received
+impl<T: WidgetBase + WidgetExt> Listener<T> {
+    /// What the widget should do on a push of `button` while exactly `modifiers` are held.
+    /// Other pushes go on as if the handler wasn't there
+    pub fn on_mouse<P: Into<Propagation>>(
+        &mut self,
+        button: MouseButton,
+        modifiers: Modifiers,
+        mut cb: impl FnMut(&mut T, &EventInfo) -> P + 'static,
+    ) -> HandlerId {
+        self.on(Event::Push, move |w, info| {
+            if info.mouse_button() == Some(button) && info.modifiers() == modifiers {
+                cb(w, info).into()
+            } else {
+                Propagation::Continue
+            }
+        })
+    }
+
+    /// What the widget should do on a right click without modifiers, like opening a context menu
+    pub fn on_right_click<P: Into<Propagation>>(
+        &mut self,
+        cb: impl FnMut(&mut T, &EventInfo) -> P + 'static,
+    ) -> HandlerId {
+        self.on_mouse(MouseButton::Right, Shortcut::None, cb)
+    }
+
+    /// What the widget should do on a middle click without modifiers
+    pub fn on_middle_click<P: Into<Propagation>>(
+        &mut self,
+        cb: impl FnMut(&mut T, &EventInfo) -> P + 'static,
+    ) -> HandlerId {
+        self.on_mouse(MouseButton::Middle, Shortcut::None, cb)
+    }
+
+    /// What the widget should do on a left click while Ctrl is held, like adding to a selection
+    pub fn on_ctrl_click<P: Into<Propagation>>(
+        &mut self,
+        cb: impl FnMut(&mut T, &EventInfo) -> P + 'static,
+    ) -> HandlerId {
+        self.on_mouse(MouseButton::Left, Shortcut::Ctrl, cb)
+    }
+}
@@ -0,0 +1,311 @@
+use crate::info::{EventInfo, TriggerInfo};
+use std::collections::VecDeque;
+
+/// Default number of events a listener keeps between polls.
+pub const DEFAULT_QUEUE_CAPACITY: usize = 64;
+
+/// What a listener's event queue does when a new event arrives and it's full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overflow {
+    /// Discard the oldest queued event to make room for the new one
+    #[default]
+    DropOldest,
+    /// Discard the incoming event, keeping what's already queued
+    DropNewest,
+    /// Replace the newest queued event if it's of the same kind (e.g. a run of [`Event::Move`](fltk::enums::Event::Move)),
+    /// otherwise discard the oldest one
+    Coalesce,
+}
+
+/// A bounded FIFO of events received by a widget.
+#[derive(Debug, Clone)]
+pub(crate) struct EventQueue {
+    events: VecDeque<EventInfo>,
+    capacity: usize,
+    overflow: Overflow,
+}
+
+impl Default for EventQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_QUEUE_CAPACITY, Overflow::default())
+    }
+}
+
+impl EventQueue {
+    pub fn new(capacity: usize, overflow: Overflow) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            events: VecDeque::with_capacity(capacity),
+            capacity,
+            overflow,
+        }
+    }
+
+    pub fn push(&mut self, ev: EventInfo) {
+        if self.events.len() < self.capacity {
+            self.events.push_back(ev);
+            return;
+        }
+        match self.overflow {
+            Overflow::DropOldest => {
+                self.events.pop_front();
+                self.events.push_back(ev);
+            }
+            Overflow::DropNewest => (),
+            Overflow::Coalesce => {
+                if let Some(last) = self.events.back_mut().filter(|last| last.event == ev.event) {
+                    *last = ev;
+                } else {
+                    self.events.pop_front();
+                    self.events.push_back(ev);
+                }
+            }
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<EventInfo> {
+        self.events.pop_front()
+    }
+
+    pub fn peek(&self) -> Option<&EventInfo> {
+        self.events.front()
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        while self.events.len() > self.capacity {
+            self.events.pop_front();
+        }
+    }
+
+    pub fn set_overflow(&mut self, overflow: Overflow) {
+        self.overflow = overflow;
+    }
+}
+
+/// The callback invocations since the last poll, counted exactly
+/// while only the latest [`DEFAULT_QUEUE_CAPACITY`] records are kept.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TriggerLog {
+    count: usize,
+    records: VecDeque<TriggerInfo>,
+}
+
+impl TriggerLog {
+    pub fn push(&mut self, info: TriggerInfo) {
+        self.count += 1;
+        if self.records.len() >= DEFAULT_QUEUE_CAPACITY {
+            self.records.pop_front();
+        }
+        self.records.push_back(info);
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Take the oldest record, the older invocations whose records were dropped are discarded
+    pub fn pop(&mut self) -> Option<TriggerInfo> {
+        let info = self.records.pop_front()?;
+        self.count = self.count.min(self.records.len());
+        Some(info)
+    }
+
+    /// Take the number of invocations, their records are kept for `pop` and `take`
+    pub fn take_count(&mut self) -> usize {
+        std::mem::take(&mut self.count)
+    }
+
+    /// Take the records of the invocations, oldest first
+    pub fn take(&mut self) -> VecDeque<TriggerInfo> {
+        self.count = 0;
+        std::mem::take(&mut self.records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fltk::enums::{CallbackReason, Event, Key, Shortcut};
+    use std::time::Instant;
+
+    /// An event told apart by its x coordinate
+    fn event(event: Event, x: i32) -> EventInfo {
+        EventInfo {
+            event,
+            x,
+            y: 0,
+            widget_x: x,
+            widget_y: 0,
+            key: Key::None,
+            button: 0,
+            state: Shortcut::None,
+            clicks: 1,
+            dx: 0,
+            dy: 0,
+            text: String::new(),
+            timestamp: Instant::now(),
+        }
+    }
+
+    fn drain(queue: &mut EventQueue) -> Vec<(Event, i32)> {
+        std::iter::from_fn(|| queue.pop())
+            .map(|info| (info.event, info.x))
+            .collect()
+    }
+
+    #[test]
+    fn pops_in_arrival_order() {
+        let mut queue = EventQueue::default();
+        queue.push(event(Event::Push, 1));
+        queue.push(event(Event::Released, 2));
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.peek().map(|info| info.x), Some(1));
+        assert_eq!(drain(&mut queue), [(Event::Push, 1), (Event::Released, 2)]);
+        assert!(queue.pop().is_none());
+    }
+
+    #[test]
+    fn drop_oldest_makes_room() {
+        let mut queue = EventQueue::new(2, Overflow::DropOldest);
+        for x in 1..=3 {
+            queue.push(event(Event::Push, x));
+        }
+        assert_eq!(drain(&mut queue), [(Event::Push, 2), (Event::Push, 3)]);
+    }
+
+    #[test]
+    fn drop_newest_keeps_queued() {
+        let mut queue = EventQueue::new(2, Overflow::DropNewest);
+        for x in 1..=3 {
+            queue.push(event(Event::Push, x));
+        }
+        assert_eq!(drain(&mut queue), [(Event::Push, 1), (Event::Push, 2)]);
+    }
+
+    #[test]
+    fn coalesce_replaces_same_kind() {
+        let mut queue = EventQueue::new(2, Overflow::Coalesce);
+        queue.push(event(Event::Push, 1));
+        for x in 2..=4 {
+            queue.push(event(Event::Move, x));
+        }
+        assert_eq!(drain(&mut queue), [(Event::Push, 1), (Event::Move, 4)]);
+        // a different kind drops the oldest instead
+        queue.push(event(Event::Move, 1));
+        queue.push(event(Event::Move, 2));
+        queue.push(event(Event::Released, 3));
+        assert_eq!(drain(&mut queue), [(Event::Move, 2), (Event::Released, 3)]);
+    }
+
+    #[test]
+    fn coalesces_only_when_full() {
+        let mut queue = EventQueue::new(3, Overflow::Coalesce);
+        queue.push(event(Event::Move, 1));
+        queue.push(event(Event::Move, 2));
+        assert_eq!(queue.len(), 2);
+    }
+
+    #[test]
+    fn shrinking_drops_oldest() {
+        let mut queue = EventQueue::new(4, Overflow::DropNewest);
+        for x in 1..=4 {
+            queue.push(event(Event::Push, x));
+        }
+        queue.set_capacity(2);
+        assert_eq!(drain(&mut queue), [(Event::Push, 3), (Event::Push, 4)]);
+        // growing back lets more in
+        queue.set_capacity(3);
+        for x in 5..=8 {
+            queue.push(event(Event::Push, x));
+        }
+        assert_eq!(queue.len(), 3);
+    }
+
+    #[test]
+    fn capacity_is_at_least_one() {
+        let mut queue = EventQueue::new(0, Overflow::DropOldest);
+        queue.push(event(Event::Push, 1));
+        queue.push(event(Event::Push, 2));
+        assert_eq!(drain(&mut queue), [(Event::Push, 2)]);
+        queue.set_capacity(0);
+        queue.push(event(Event::Push, 3));
+        assert_eq!(queue.len(), 1);
+    }
+
+    #[test]
+    fn overflow_can_change() {
+        let mut queue = EventQueue::new(1, Overflow::DropNewest);
+        queue.push(event(Event::Push, 1));
+        queue.set_overflow(Overflow::DropOldest);
+        queue.push(event(Event::Push, 2));
+        assert_eq!(drain(&mut queue), [(Event::Push, 2)]);
+    }
+
+    /// A trigger told apart by its button
+    fn trigger(button: i32) -> TriggerInfo {
+        TriggerInfo {
+            reason: CallbackReason::Changed,
+            event: Event::Released,
+            key: Key::None,
+            button,
+            state: Shortcut::None,
+            timestamp: Instant::now(),
+        }
+    }
+
+    fn buttons(records: impl IntoIterator<Item = TriggerInfo>) -> Vec<i32> {
+        records.into_iter().map(|info| info.button).collect()
+    }
+
+    #[test]
+    fn counts_and_records_triggers() {
+        let mut log = TriggerLog::default();
+        log.push(trigger(1));
+        log.push(trigger(2));
+        assert_eq!(log.count(), 2);
+        assert_eq!(buttons(log.pop()), [1]);
+        assert_eq!(log.count(), 1);
+        assert_eq!(buttons(log.take()), [2]);
+        assert_eq!(log.count(), 0);
+        assert!(log.pop().is_none());
+    }
+
+    #[test]
+    fn counts_past_the_kept_records() {
+        let mut log = TriggerLog::default();
+        let n = DEFAULT_QUEUE_CAPACITY as i32 + 10;
+        for button in 0..n {
+            log.push(trigger(button));
+        }
+        assert_eq!(log.count(), n as usize);
+        let records = buttons(log.take());
+        assert_eq!(records.len(), DEFAULT_QUEUE_CAPACITY);
+        assert_eq!(records.first(), Some(&10));
+        assert_eq!(records.last(), Some(&(n - 1)));
+    }
+
+    #[test]
+    fn popping_discards_dropped_invocations() {
+        let mut log = TriggerLog::default();
+        for button in 0..DEFAULT_QUEUE_CAPACITY as i32 + 10 {
+            log.push(trigger(button));
+        }
+        assert_eq!(buttons(log.pop()), [10]);
+        assert_eq!(log.count(), DEFAULT_QUEUE_CAPACITY - 1);
+    }
+
+    #[test]
+    fn taking_the_count_keeps_records() {
+        let mut log = TriggerLog::default();
+        log.push(trigger(1));
+        log.push(trigger(2));
+        assert_eq!(log.take_count(), 2);
+        assert_eq!(log.count(), 0);
+        assert_eq!(buttons(log.take()), [1, 2]);
+    }
+}
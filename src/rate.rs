@@ -0,0 +1,161 @@
+use fltk::app;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// How often a listener lets triggers and events through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Rate {
+    /// Only the last of a burst, once nothing happened for the duration
+    Debounce(Duration),
+    /// The first of a burst right after it, then the latest at most once per duration
+    Throttle(Duration),
+}
+
+struct Slot<I> {
+    pending: Option<I>,
+    deadline: Instant,
+    scheduled: bool,
+    last_run: Option<Instant>,
+}
+
+impl<I> Default for Slot<I> {
+    fn default() -> Self {
+        Self {
+            pending: None,
+            deadline: Instant::now(),
+            scheduled: false,
+            last_run: None,
+        }
+    }
+}
+
+/// Delays and drops items according to a [`Rate`], separately for each key,
+/// delivering the ones let through from the event loop's timeouts.
+pub(crate) struct Limiter<K, I> {
+    rate: Rate,
+    slots: Rc<RefCell<HashMap<K, Slot<I>>>>,
+    deliver: Rc<dyn Fn(I)>,
+}
+
+impl<K, I> Clone for Limiter<K, I> {
+    fn clone(&self) -> Self {
+        Self {
+            rate: self.rate,
+            slots: self.slots.clone(),
+            deliver: self.deliver.clone(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Copy + 'static, I: 'static> Limiter<K, I> {
+    pub fn new(rate: Rate, deliver: impl Fn(I) + 'static) -> Self {
+        Self {
+            rate,
+            slots: Rc::default(),
+            deliver: Rc::new(deliver),
+        }
+    }
+
+    pub fn submit(&self, key: K, item: I) {
+        let now = Instant::now();
+        let mut slots = self.slots.borrow_mut();
+        let slot = slots.entry(key).or_default();
+        let delay = match self.rate {
+            Rate::Debounce(delay) => {
+                slot.deadline = now + delay;
+                delay
+            }
+            Rate::Throttle(interval) => {
+                let next_run = slot.last_run.map_or(now, |last| last + interval);
+                if !slot.scheduled && next_run <= now {
+                    slot.last_run = Some(now);
+                    // delivered from the event loop, once the widget is done handling the event
+                    let deliver = self.deliver.clone();
+                    let mut item = Some(item);
+                    app::add_timeout3(0.0, move |_| {
+                        if let Some(item) = item.take() {
+                            deliver(item);
+                        }
+                    });
+                    return;
+                }
+                slot.deadline = next_run;
+                next_run - now
+            }
+        };
+        slot.pending = Some(item);
+        if !slot.scheduled {
+            slot.scheduled = true;
+            let limiter = self.clone();
+            app::add_timeout3(delay.as_secs_f64(), move |handle| limiter.fire(key, handle));
+        }
+    }
+
+    /// Deliver the pending item once its deadline passed, the timeout is reused while it's pushed back
+    fn fire(&self, key: K, handle: app::TimeoutHandle) {
+        let now = Instant::now();
+        let mut slots = self.slots.borrow_mut();
+        let Some(slot) = slots.get_mut(&key) else {
+            return;
+        };
+        if now < slot.deadline {
+            app::repeat_timeout3((slot.deadline - now).as_secs_f64(), handle);
+            return;
+        }
+        slot.scheduled = false;
+        slot.last_run = Some(now);
+        let item = slot.pending.take();
+        drop(slots);
+        if let Some(item) = item {
+            (self.deliver)(item);
+        }
+    }
+}
+
+/// A pending timeout owned by a handler, removed when the handler is unregistered along with it.
+#[derive(Default)]
+pub(crate) struct Timeout(Rc<Cell<Option<app::TimeoutHandle>>>);
+
+impl Timeout {
+    /// Run `cb` once after `delay`, in place of the pending one
+    pub fn start(&self, delay: Duration, mut cb: impl FnMut() + 'static) {
+        self.cancel();
+        let handle = self.0.clone();
+        self.0
+            .set(Some(app::add_timeout3(delay.as_secs_f64(), move |_| {
+                handle.set(None);
+                cb();
+            })));
+    }
+
+    /// Run `cb` every `interval` for as long as it returns true, in place of the pending timeout
+    pub fn start_repeating(&self, interval: Duration, mut cb: impl FnMut() -> bool + 'static) {
+        self.cancel();
+        let handle = self.0.clone();
+        self.0.set(Some(app::add_timeout3(
+            interval.as_secs_f64(),
+            move |timeout| {
+                if cb() {
+                    app::repeat_timeout3(interval.as_secs_f64(), timeout);
+                } else {
+                    handle.set(None);
+                }
+            },
+        )));
+    }
+
+    pub fn cancel(&self) {
+        if let Some(handle) = self.0.take() {
+            app::remove_timeout3(handle);
+        }
+    }
+}
+
+impl Drop for Timeout {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
+
+/// A boxed future handed to a [`Spawner`].
+pub type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Hands futures started by an [`AsyncListener`](crate::AsyncListener) to an async runtime.
+/// [`AsyncListener`](crate::AsyncListener) itself only relies on wakers and works on any executor,
+/// a spawner is needed only for the handlers that start futures of their own.
+///
+/// Any `Fn(BoxFuture)` closure is a spawner, for example to use a `futures::executor::ThreadPool`:
+/// ```rust,ignore
+/// let pool = futures::executor::ThreadPool::new().unwrap();
+/// let spawner = move |fut| pool.spawn_ok(fut);
+/// ```
+pub trait Spawner: Send + Sync + 'static {
+    /// Run the future to completion in the background
+    fn spawn(&self, fut: BoxFuture);
+}
+
+impl<F: Fn(BoxFuture) + Send + Sync + 'static> Spawner for F {
+    fn spawn(&self, fut: BoxFuture) {
+        self(fut)
+    }
+}
+
+/// Spawns on the tokio runtime of the calling thread, see [`tokio::spawn`].
+#[cfg(feature = "tokio")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tokio;
+
+#[cfg(feature = "tokio")]
+impl Spawner for Tokio {
+    fn spawn(&self, fut: BoxFuture) {
+        tokio::spawn(fut);
+    }
+}
+
+/// Spawns on the async-std global executor, see [`async_std::task::spawn`].
+#[cfg(feature = "async-std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AsyncStd;
+
+#[cfg(feature = "async-std")]
+impl Spawner for AsyncStd {
+    fn spawn(&self, fut: BoxFuture) {
+        async_std::task::spawn(fut);
+    }
+}
+
+/// Spawns on the smol global executor, see [`smol::spawn`].
+#[cfg(feature = "smol")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Smol;
+
+#[cfg(feature = "smol")]
+impl Spawner for Smol {
+    fn spawn(&self, fut: BoxFuture) {
+        smol::spawn(fut).detach();
+    }
+}
+
+/// Spawns on tokio from within a tokio runtime, on the next enabled runtime otherwise,
+/// the default when several runtime features are enabled.
+#[cfg(feature = "tokio")]
+struct TokioOrNext;
+
+#[cfg(feature = "tokio")]
+impl Spawner for TokioOrNext {
+    fn spawn(&self, fut: BoxFuture) {
+        if tokio::runtime::Handle::try_current().is_ok() {
+            return Tokio.spawn(fut);
+        }
+        #[cfg(feature = "async-std")]
+        return AsyncStd.spawn(fut);
+        #[cfg(all(feature = "smol", not(feature = "async-std")))]
+        return Smol.spawn(fut);
+        // panics, reporting that there's no tokio runtime
+        #[allow(unreachable_code)]
+        Tokio.spawn(fut)
+    }
+}
+
+/// The spawner a listener starts with, picked from the enabled runtime features
+pub(crate) fn default_spawner() -> Option<Arc<dyn Spawner>> {
+    #[cfg(feature = "tokio")]
+    return Some(Arc::new(TokioOrNext));
+    #[cfg(all(feature = "async-std", not(feature = "tokio")))]
+    return Some(Arc::new(AsyncStd));
+    #[cfg(all(feature = "smol", not(any(feature = "tokio", feature = "async-std"))))]
+    return Some(Arc::new(Smol));
+    #[allow(unreachable_code)]
+    None
+}
+
+/// What [`AsyncListener::on_async_with`](crate::AsyncListener::on_async_with) does
+/// when an event arrives while the future started by a previous one is still running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overlap {
+    /// Run the futures side by side
+    #[default]
+    Concurrent,
+    /// Run the futures one after the other, in event order
+    Queue,
+    /// Drop the running future and start the new one
+    CancelPrevious,
+}
+
+/// Starts the futures of one `on_async` handler according to its [`Overlap`] policy.
+pub(crate) enum Runner {
+    Concurrent,
+    Queue(Arc<Mutex<Pending>>),
+    CancelPrevious(Arc<Mutex<Generation>>),
+}
+
+#[derive(Default)]
+pub(crate) struct Pending {
+    futures: VecDeque<BoxFuture>,
+    running: bool,
+}
+
+#[derive(Default)]
+pub(crate) struct Generation {
+    current: u64,
+    waker: Option<Waker>,
+}
+
+impl From<Overlap> for Runner {
+    fn from(overlap: Overlap) -> Self {
+        match overlap {
+            Overlap::Concurrent => Runner::Concurrent,
+            Overlap::Queue => Runner::Queue(Arc::default()),
+            Overlap::CancelPrevious => Runner::CancelPrevious(Arc::default()),
+        }
+    }
+}
+
+impl Runner {
+    pub fn run(&self, spawner: &dyn Spawner, fut: BoxFuture) {
+        match self {
+            Runner::Concurrent => spawner.spawn(fut),
+            Runner::Queue(pending) => {
+                let start = {
+                    let mut state = pending.lock().unwrap();
+                    state.futures.push_back(fut);
+                    !std::mem::replace(&mut state.running, true)
+                };
+                if start {
+                    spawner.spawn(Box::pin(drain(pending.clone())));
+                }
+            }
+            Runner::CancelPrevious(generation) => {
+                let gen = {
+                    let mut state = generation.lock().unwrap();
+                    state.current += 1;
+                    if let Some(waker) = state.waker.take() {
+                        waker.wake();
+                    }
+                    state.current
+                };
+                spawner.spawn(Box::pin(cancellable(generation.clone(), gen, fut)));
+            }
+        }
+    }
+}
+
+/// Run the queued futures one at a time until the queue is empty
+async fn drain(pending: Arc<Mutex<Pending>>) {
+    loop {
+        let fut = {
+            let mut state = pending.lock().unwrap();
+            match state.futures.pop_front() {
+                Some(fut) => fut,
+                None => {
+                    state.running = false;
+                    return;
+                }
+            }
+        };
+        fut.await;
+    }
+}
+
+/// Run a future until it completes or a newer generation starts
+fn cancellable(
+    generation: Arc<Mutex<Generation>>,
+    gen: u64,
+    mut fut: BoxFuture,
+) -> impl Future<Output = ()> + Send + 'static {
+    std::future::poll_fn(move |cx| {
+        {
+            let mut state = generation.lock().unwrap();
+            if state.current != gen {
+                return Poll::Ready(());
+            }
+            state.waker = Some(cx.waker().clone());
+        }
+        fut.as_mut().poll(cx)
+    })
+}
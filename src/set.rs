@@ -0,0 +1,201 @@
+use crate::blocking::Listener;
+use crate::info::TriggerInfo;
+use fltk::prelude::{WidgetBase, WidgetExt};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::task::{Poll, Waker};
+
+/// A listener registered in a [`ListenerSet`].
+trait Member {
+    /// Drain the trigger records since the last poll, oldest first
+    fn take_triggers(&self) -> VecDeque<TriggerInfo>;
+
+    /// Wake the task on the next trigger, if the listener supports it
+    fn register(&self, _waker: &Waker) {}
+}
+
+impl<T: WidgetBase + WidgetExt> Member for Listener<T> {
+    fn take_triggers(&self) -> VecDeque<TriggerInfo> {
+        self.triggers().collect()
+    }
+
+    fn register(&self, waker: &Waker) {
+        self.register_trigger_waker(waker);
+    }
+}
+
+#[cfg(any(
+    feature = "tokio",
+    feature = "async-std",
+    feature = "smol",
+    feature = "futures"
+))]
+impl<T: WidgetBase + WidgetExt + 'static> Member for crate::AsyncListener<T> {
+    fn take_triggers(&self) -> VecDeque<TriggerInfo> {
+        self.take_triggers()
+    }
+
+    fn register(&self, waker: &Waker) {
+        self.register_trigger_waker(waker);
+    }
+}
+
+/// Polls many listeners at once, each registered under a key like an enum variant.
+/// The set consumes the triggers of its listeners, they shouldn't be polled individually.
+/// ```rust,no_run
+/// use fltk::{app, button, prelude::*, window};
+/// use fltk_evented::{Listener, ListenerSet};
+///
+/// #[derive(Clone, Copy)]
+/// enum Msg {
+///     Inc,
+///     Dec,
+/// }
+///
+/// let app = app::App::default();
+/// let mut wind = window::Window::default().with_size(400, 300);
+/// let inc: Listener<_> = button::Button::new(160, 50, 80, 35, "+").into();
+/// let dec: Listener<_> = button::Button::new(160, 210, 80, 35, "-").into();
+/// wind.end();
+/// wind.show();
+///
+/// let set = ListenerSet::new().with(Msg::Inc, &inc).with(Msg::Dec, &dec);
+/// let mut count = 0;
+/// while app.wait() {
+///     for (msg, _) in set.poll() {
+///         match msg {
+///             Msg::Inc => count += 1,
+///             Msg::Dec => count -= 1,
+///         }
+///     }
+/// }
+/// ```
+pub struct ListenerSet<K> {
+    members: Vec<(K, Box<dyn Member>)>,
+    pending: RefCell<VecDeque<(K, TriggerInfo)>>,
+}
+
+impl<K> Default for ListenerSet<K> {
+    fn default() -> Self {
+        Self {
+            members: Vec::new(),
+            pending: RefCell::new(VecDeque::new()),
+        }
+    }
+}
+
+impl<K: Clone> ListenerSet<K> {
+    /// Creates an empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a listener under a key
+    pub fn insert<T: WidgetBase + WidgetExt + Clone + 'static>(
+        &mut self,
+        key: K,
+        listener: &Listener<T>,
+    ) {
+        self.members.push((key, Box::new(listener.clone())));
+    }
+
+    /// Initialize with a listener registered under a key
+    pub fn with<T: WidgetBase + WidgetExt + Clone + 'static>(
+        mut self,
+        key: K,
+        listener: &Listener<T>,
+    ) -> Self {
+        self.insert(key, listener);
+        self
+    }
+
+    /// Register an async listener under a key
+    #[cfg(any(
+        feature = "tokio",
+        feature = "async-std",
+        feature = "smol",
+        feature = "futures"
+    ))]
+    pub fn insert_async<T: WidgetBase + WidgetExt + Clone + 'static>(
+        &mut self,
+        key: K,
+        listener: &crate::AsyncListener<T>,
+    ) {
+        self.members.push((key, Box::new(listener.clone())));
+    }
+
+    /// Initialize with an async listener registered under a key
+    #[cfg(any(
+        feature = "tokio",
+        feature = "async-std",
+        feature = "smol",
+        feature = "futures"
+    ))]
+    pub fn with_async<T: WidgetBase + WidgetExt + Clone + 'static>(
+        mut self,
+        key: K,
+        listener: &crate::AsyncListener<T>,
+    ) -> Self {
+        self.insert_async(key, listener);
+        self
+    }
+
+    /// Number of registered listeners
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Whether no listeners are registered
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Drain the triggers of all listeners since the last poll, in the order the callbacks ran.
+    /// A listener triggered several times appears once per callback invocation
+    pub fn poll(&self) -> impl Iterator<Item = (K, TriggerInfo)> {
+        self.collect();
+        std::mem::take(&mut *self.pending.borrow_mut()).into_iter()
+    }
+
+    /// Wait for the next trigger of any listener, resolves at once if triggers are pending.
+    /// Blocking listeners wake the waiting task from the UI thread, like async ones
+    pub async fn next(&self) -> (K, TriggerInfo) {
+        std::future::poll_fn(|cx| {
+            if let Some(next) = self.pop() {
+                return Poll::Ready(next);
+            }
+            for (_, member) in &self.members {
+                member.register(cx.waker());
+            }
+            // a trigger might have landed before the wakers were registered
+            match self.pop() {
+                Some(next) => Poll::Ready(next),
+                None => Poll::Pending,
+            }
+        })
+        .await
+    }
+
+    fn pop(&self) -> Option<(K, TriggerInfo)> {
+        if self.pending.borrow().is_empty() {
+            self.collect();
+        }
+        self.pending.borrow_mut().pop_front()
+    }
+
+    /// Move the triggers of all listeners into the pending queue, ordered by when they happened
+    fn collect(&self) {
+        let mut triggers: Vec<_> = self
+            .members
+            .iter()
+            .flat_map(|(key, member)| {
+                member
+                    .take_triggers()
+                    .into_iter()
+                    .map(move |info| (key.clone(), info))
+            })
+            .collect();
+        triggers.sort_by_key(|(_, info)| info.timestamp);
+        self.pending.borrow_mut().extend(triggers);
+    }
+}
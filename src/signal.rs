@@ -0,0 +1,301 @@
+use crate::base::BaseListener;
+use crate::blocking::Listener;
+use crate::value::{sealed::ValueSet, HasValue};
+use fltk::{
+    app,
+    prelude::{WidgetBase, WidgetExt},
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::str::FromStr;
+
+/// What a two-way binding does when the widget holds a value that doesn't parse, see [`Listener::bind_to_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InvalidInput {
+    /// Put the model's value back in the widget
+    #[default]
+    Revert,
+    /// Leave the widget as is, the model keeps its last valid value
+    Keep,
+}
+
+type Binding<T> = Box<dyn FnMut(&T) -> bool>;
+
+struct Inner<T> {
+    value: Rc<T>,
+    bindings: Vec<Binding<T>>,
+    scheduled: bool,
+    /// The bindings are running, `dirty` when the value changed meanwhile
+    notifying: bool,
+    dirty: bool,
+}
+
+/// A value that widgets bind to, like with [`BaseListener::bind_label`].
+/// Changing it updates and redraws the bound widgets on the next iteration of the event loop.
+/// Clones share the same value.
+/// ```rust,no_run
+/// use fltk::{app, button, frame, prelude::*, window};
+/// use fltk_evented::{Listener, Signal};
+///
+/// let app = app::App::default();
+/// let mut wind = window::Window::default().with_size(160, 200);
+/// let inc: Listener<_> = button::Button::new(40, 20, 80, 40, "+").into();
+/// let frame: Listener<_> = frame::Frame::new(40, 80, 80, 40, None).into();
+/// let dec: Listener<_> = button::Button::new(40, 140, 80, 40, "-").into();
+/// wind.end();
+/// wind.show();
+///
+/// let count = Signal::new(0);
+/// frame.bind_label(&count, |c| c.to_string());
+/// dec.bind_active(&count, |c| *c > 0);
+///
+/// while app.wait() {
+///     if inc.triggered() {
+///         count.update(|c| *c += 1);
+///     }
+///     if dec.triggered() {
+///         count.update(|c| *c -= 1);
+///     }
+/// }
+/// ```
+pub struct Signal<T> {
+    inner: Rc<RefCell<Inner<T>>>,
+}
+
+impl<T> Clone for Signal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: Default + 'static> Default for Signal<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Signal<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Signal")
+            .field(&self.inner.borrow().value)
+            .finish()
+    }
+}
+
+impl<T: 'static> Signal<T> {
+    /// Creates a signal holding a value
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(Inner {
+                value: Rc::new(value),
+                bindings: Vec::new(),
+                scheduled: false,
+                notifying: false,
+                dirty: false,
+            })),
+        }
+    }
+
+    /// Run a closure with the current value
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        let value = self.inner.borrow().value.clone();
+        f(&value)
+    }
+
+    /// Replace the value, the bound widgets are updated on the next event loop iteration
+    pub fn set(&self, value: T) {
+        self.inner.borrow_mut().value = Rc::new(value);
+        self.schedule();
+    }
+
+    /// Bind a closure run with the value now and on every change, until it returns false
+    pub fn bind(&self, mut f: impl FnMut(&T) -> bool + 'static) {
+        let value = self.inner.borrow().value.clone();
+        if f(&value) {
+            self.inner.borrow_mut().bindings.push(Box::new(f));
+        }
+    }
+
+    /// Run the bindings once at the next event loop iteration, however many changes happen until then
+    fn schedule(&self) {
+        let mut inner = self.inner.borrow_mut();
+        // the running bindings are taken out of `bindings`, they're run again once done
+        if inner.notifying {
+            inner.dirty = true;
+            return;
+        }
+        if inner.scheduled || inner.bindings.is_empty() {
+            return;
+        }
+        inner.scheduled = true;
+        let signal = self.clone();
+        app::add_timeout3(0.0, move |_| signal.notify());
+    }
+
+    fn notify(&self) {
+        let (value, mut bindings) = {
+            let mut inner = self.inner.borrow_mut();
+            inner.scheduled = false;
+            inner.notifying = true;
+            (inner.value.clone(), std::mem::take(&mut inner.bindings))
+        };
+        // bindings may change the signal or bind new ones while running
+        bindings.retain_mut(|f| f(&value));
+        let dirty = {
+            let mut inner = self.inner.borrow_mut();
+            bindings.append(&mut inner.bindings);
+            inner.bindings = bindings;
+            inner.notifying = false;
+            std::mem::take(&mut inner.dirty)
+        };
+        if dirty {
+            self.schedule();
+        }
+    }
+}
+
+impl<T: Clone + 'static> Signal<T> {
+    /// Get a copy of the current value
+    pub fn get(&self) -> T {
+        self.with(T::clone)
+    }
+
+    /// Modify a copy of the value then store it, the bound widgets are updated on the next event loop iteration.
+    /// The closure may read the signal, like `count.update(|c| *c = count.get() + 1)`
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        let mut value = self.get();
+        f(&mut value);
+        self.set(value);
+    }
+}
+
+/// Signal bindings, the widget follows the signal until it's deleted
+impl<T: WidgetBase + WidgetExt + Clone + 'static, TRIG: ValueSet<T> + Clone + 'static>
+    BaseListener<T, TRIG>
+{
+    /// Set the widget's label from the signal's value
+    pub fn bind_label<V: 'static>(&self, signal: &Signal<V>, f: impl Fn(&V) -> String + 'static) {
+        let mut wid = self.wid.clone();
+        signal.bind(move |v| {
+            if wid.was_deleted() {
+                return false;
+            }
+            wid.set_label(&f(v));
+            wid.redraw();
+            true
+        });
+    }
+
+    /// Set the widget's value from the signal's value, see [`HasValue`]
+    pub fn bind_value<V: 'static>(&self, signal: &Signal<V>, f: impl Fn(&V) -> T::Value + 'static)
+    where
+        T: HasValue,
+    {
+        let mut wid = self.wid.clone();
+        let trig = self.trig.clone();
+        signal.bind(move |v| {
+            if wid.was_deleted() {
+                return false;
+            }
+            wid.set_current_value(f(v));
+            trig.value_set(&wid);
+            wid.redraw();
+            true
+        });
+    }
+
+    /// Activate the widget while the predicate holds for the signal's value, deactivate it otherwise
+    pub fn bind_active<V: 'static>(&self, signal: &Signal<V>, pred: impl Fn(&V) -> bool + 'static) {
+        let mut wid = self.wid.clone();
+        signal.bind(move |v| {
+            if wid.was_deleted() {
+                return false;
+            }
+            if pred(v) {
+                wid.activate();
+            } else {
+                wid.deactivate();
+            }
+            wid.redraw();
+            true
+        });
+    }
+}
+
+/// Two-way bindings, the widget and the signal follow each other
+impl<T: WidgetBase + WidgetExt + HasValue + Clone + 'static> Listener<T> {
+    /// Keep the widget's value and the signal in sync, in both directions.
+    /// The signal is updated when the widget's callback runs, see [`WidgetExt::set_trigger`]
+    pub fn bind_to(&mut self, model: &Signal<T::Value>) {
+        self.bind_to_with(
+            model,
+            |v| Some(v.clone()),
+            T::Value::clone,
+            InvalidInput::Keep,
+        );
+    }
+
+    /// Keep a text widget, like an `IntInput`, and a signal holding the parsed value in sync,
+    /// using [`FromStr`] and [`ToString`]
+    /// ```rust,no_run
+    /// use fltk::{input, prelude::*};
+    /// use fltk_evented::{InvalidInput, Listener, Signal};
+    ///
+    /// let age = Signal::new(42);
+    /// let mut inp: Listener<_> = input::IntInput::new(10, 10, 80, 30, "Age").into();
+    /// inp.bind_parsed(&age, InvalidInput::Revert);
+    /// ```
+    pub fn bind_parsed<V>(&mut self, model: &Signal<V>, invalid: InvalidInput)
+    where
+        T: HasValue<Value = String>,
+        V: FromStr + ToString + Clone + PartialEq + 'static,
+    {
+        self.bind_to_with(model, |s| s.trim().parse().ok(), V::to_string, invalid);
+    }
+
+    /// Keep the widget's value and the signal in sync, in both directions.
+    /// `parse` reads the model's value from the widget's, `format` does the reverse,
+    /// `invalid` decides what happens when `parse` fails
+    pub fn bind_to_with<V: Clone + PartialEq + 'static>(
+        &mut self,
+        model: &Signal<V>,
+        parse: impl Fn(&T::Value) -> Option<V> + 'static,
+        format: impl Fn(&V) -> T::Value + 'static,
+        invalid: InvalidInput,
+    ) {
+        let parse = Rc::new(parse);
+        let format = Rc::new(format);
+        self.on_trigger({
+            let model = model.clone();
+            let parse = parse.clone();
+            let format = format.clone();
+            move |w, _| match parse(&w.current_value()) {
+                Some(v) => {
+                    if model.with(|m| *m != v) {
+                        model.set(v);
+                    }
+                }
+                None => {
+                    if invalid == InvalidInput::Revert {
+                        w.set_current_value(model.with(|m| format(m)));
+                        w.redraw();
+                    }
+                }
+            }
+        });
+        let mut wid = self.wid.clone();
+        model.bind(move |v| {
+            if wid.was_deleted() {
+                return false;
+            }
+            // leave the widget alone when it already holds the value, possibly formatted differently
+            if parse(&wid.current_value()).as_ref() != Some(v) {
+                wid.set_current_value(format(v));
+                wid.redraw();
+            }
+            true
+        });
+    }
+}
@@ -0,0 +1,227 @@
+use crate::base::BaseListener;
+use fltk::{
+    button, input, menu, misc,
+    prelude::{ButtonExt, DisplayExt, InputExt, MenuExt, ValuatorExt, WidgetBase, WidgetExt},
+    text, valuator,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A widget holding a value a [`ValueListener`] can watch.
+pub trait HasValue {
+    /// The type of the widget's value
+    type Value: Clone + PartialEq + 'static;
+
+    /// Read the widget's current value
+    fn current_value(&self) -> Self::Value;
+
+    /// Set the widget's value, without running its callback
+    fn set_current_value(&mut self, value: Self::Value);
+}
+
+macro_rules! impl_has_value {
+    ($value:ty, |$wid:ident, $val:ident| $set:expr; $($widget:ty),* $(,)?) => {
+        $(
+            impl HasValue for $widget {
+                type Value = $value;
+
+                fn current_value(&self) -> $value {
+                    self.value()
+                }
+
+                fn set_current_value(&mut self, $val: $value) {
+                    let $wid = self;
+                    $set;
+                }
+            }
+        )*
+    };
+}
+
+impl_has_value!(f64, |w, v| w.set_value(v);
+    valuator::Slider,
+    valuator::NiceSlider,
+    valuator::ValueSlider,
+    valuator::FillSlider,
+    valuator::HorSlider,
+    valuator::HorNiceSlider,
+    valuator::HorValueSlider,
+    valuator::HorFillSlider,
+    valuator::Dial,
+    valuator::LineDial,
+    valuator::FillDial,
+    valuator::Counter,
+    valuator::SimpleCounter,
+    valuator::Scrollbar,
+    valuator::Roller,
+    valuator::Adjuster,
+    valuator::ValueInput,
+    valuator::ValueOutput,
+    misc::Spinner,
+);
+
+impl_has_value!(String, |w, v| w.set_value(&v);
+    input::Input,
+    input::IntInput,
+    input::FloatInput,
+    input::MultilineInput,
+    input::SecretInput,
+    input::FileInput,
+);
+
+impl_has_value!(bool, |w, v| w.set_value(v);
+    button::CheckButton,
+    button::RoundButton,
+    button::LightButton,
+    button::ToggleButton,
+    button::RadioButton,
+    button::RadioRoundButton,
+    button::RadioLightButton,
+);
+
+impl_has_value!(i32, |w, v| w.set_value(v); menu::Choice);
+
+impl_has_value!(Option<String>, |w, v| match v {
+    Some(v) => w.set_value(&v),
+    None => w.set_value_index(-1),
+}; misc::InputChoice);
+
+impl HasValue for text::TextEditor {
+    type Value = String;
+
+    fn current_value(&self) -> String {
+        self.buffer().map(|buf| buf.text()).unwrap_or_default()
+    }
+
+    fn set_current_value(&mut self, value: String) {
+        match self.buffer() {
+            Some(mut buf) => buf.set_text(&value),
+            None => {
+                let mut buf = text::TextBuffer::default();
+                buf.set_text(&value);
+                self.set_buffer(buf);
+            }
+        }
+    }
+}
+
+type ChangeFn<V> = Box<dyn FnMut(&V, &V)>;
+type ChangeHandlers<V> = Rc<RefCell<Vec<Rc<RefCell<ChangeFn<V>>>>>>;
+
+pub struct ValueTrig<T, V> {
+    getter: Rc<dyn Fn(&T) -> V>,
+    value: Rc<RefCell<V>>,
+    change: Rc<RefCell<Option<(V, V)>>>,
+    handlers: ChangeHandlers<V>,
+}
+
+impl<T, V> Clone for ValueTrig<T, V> {
+    fn clone(&self) -> Self {
+        Self {
+            getter: self.getter.clone(),
+            value: self.value.clone(),
+            change: self.change.clone(),
+            handlers: self.handlers.clone(),
+        }
+    }
+}
+
+impl<T, V: std::fmt::Debug> std::fmt::Debug for ValueTrig<T, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ValueTrig")
+            .field("value", &self.value)
+            .field("change", &self.change)
+            .finish_non_exhaustive()
+    }
+}
+
+pub(crate) mod sealed {
+    /// Lets a listener catch up with a value set without running the widget's callback
+    pub trait ValueSet<T> {
+        fn value_set(&self, _wid: &T) {}
+    }
+}
+
+impl<T, V> sealed::ValueSet<T> for ValueTrig<T, V> {
+    fn value_set(&self, wid: &T) {
+        *self.value.borrow_mut() = (self.getter)(wid);
+    }
+}
+
+/// The value listener reports how a widget's value changed since the last poll from [`ValueListener::changed()`],
+/// and runs the [`ValueListener::on_change()`] callbacks as it changes.
+/// Changes are detected when the widget's callback runs, see [`WidgetExt::set_trigger`]
+/// to report an input's edits as they're typed.
+/// A value set without running the callback, like with `set_value`, is the old value of the next change
+/// once [`ValueListener::resync()`] is called, [`BaseListener::bind_value`] does so by itself.
+pub type ValueListener<T, V = <T as HasValue>::Value> = BaseListener<T, ValueTrig<T, V>>;
+
+/// core constructor
+impl<T: WidgetBase + WidgetExt + HasValue + 'static> From<T> for ValueListener<T> {
+    fn from(wid: T) -> Self {
+        Self::from_getter(wid, T::current_value)
+    }
+}
+
+impl<T: WidgetBase + WidgetExt + 'static, V: Clone + PartialEq + 'static> ValueListener<T, V> {
+    /// Watch a value read from the widget by `getter`, for widgets without a [`HasValue`] adapter
+    /// or to watch a derived value, like `|s: &valuator::Slider| s.value() as i32`
+    pub fn from_getter(mut wid: T, getter: impl Fn(&T) -> V + 'static) -> Self {
+        let trig = ValueTrig {
+            value: Rc::new(RefCell::new(getter(&wid))),
+            getter: Rc::new(getter),
+            change: Rc::new(RefCell::new(None)),
+            handlers: Rc::default(),
+        };
+        wid.set_callback({
+            let trig = trig.clone();
+            move |w| {
+                let new = (trig.getter)(w);
+                if *trig.value.borrow() == new {
+                    return;
+                }
+                let old = trig.value.replace(new.clone());
+                trig.change
+                    .borrow_mut()
+                    .get_or_insert_with(|| (old.clone(), new.clone()))
+                    .1 = new.clone();
+                let handlers = trig.handlers.borrow().clone();
+                for cb in handlers {
+                    if let Ok(mut cb) = cb.try_borrow_mut() {
+                        cb(&old, &new);
+                    }
+                }
+            }
+        });
+        Self { wid, trig }
+    }
+}
+
+/// core implementation
+impl<T: WidgetBase + WidgetExt, V: Clone + PartialEq> ValueListener<T, V> {
+    /// Get the value before and after the changes since the last poll, as `(old, new)`,
+    /// returns `None` if the value didn't change or changed back
+    pub fn changed(&self) -> Option<(V, V)> {
+        self.trig
+            .change
+            .borrow_mut()
+            .take()
+            .filter(|(old, new)| old != new)
+    }
+
+    /// Re-read the value from the widget after setting it without running the callback,
+    /// so that the next change is reported from it
+    pub fn resync(&self) {
+        *self.trig.value.borrow_mut() = (self.trig.getter)(&self.wid);
+    }
+
+    /// What to do when the value changes, the callback gets the old and new value.
+    /// Callbacks stack, they run in registration order
+    pub fn on_change(&mut self, cb: impl FnMut(&V, &V) + 'static) {
+        let cb: ChangeFn<V> = Box::new(cb);
+        self.trig
+            .handlers
+            .borrow_mut()
+            .push(Rc::new(RefCell::new(cb)));
+    }
+}